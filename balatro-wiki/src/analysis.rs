@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use crate::database::{ModDatabase, ModInfo};
+
+/// A mod's dependencies, resolved recursively against the database.
+#[derive(Debug, Clone)]
+pub struct DependencyNode {
+    pub name: String,
+    /// False when the dependency isn't in the database (e.g. not on the wiki).
+    pub found: bool,
+    /// True when this dependency already appears higher up the tree.
+    pub cycle: bool,
+    pub children: Vec<DependencyNode>,
+}
+
+/// Builds the depth-first dependency tree rooted at `mod_info`.
+pub fn dependency_tree(db: &ModDatabase, mod_info: &ModInfo) -> DependencyNode {
+    let mut path = HashSet::new();
+    build_node(db, &mod_info.name, &mut path)
+}
+
+fn build_node(db: &ModDatabase, name: &str, path: &mut HashSet<String>) -> DependencyNode {
+    let key = name.to_lowercase();
+    let Some(mod_info) = db.find(name) else {
+        return DependencyNode { name: name.to_string(), found: false, cycle: false, children: Vec::new() };
+    };
+    if path.contains(&key) {
+        return DependencyNode { name: mod_info.name.clone(), found: true, cycle: true, children: Vec::new() };
+    }
+    
+    path.insert(key.clone());
+    let children = mod_info.dependencies.iter()
+        .map(|dep| build_node(db, dep, path))
+        .collect();
+    path.remove(&key);
+    
+    DependencyNode { name: mod_info.name.clone(), found: true, cycle: false, children }
+}
+
+/// Returns the mods that list `name` as a dependency, sorted by name.
+pub fn dependents<'a>(db: &'a ModDatabase, name: &str) -> Vec<&'a ModInfo> {
+    let name_lower = name.to_lowercase();
+    let mut result: Vec<&ModInfo> = db.mods.values()
+        .filter(|m| m.dependencies.iter().any(|d| d.to_lowercase() == name_lower))
+        .collect();
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    result
+}
+
+/// Ranks `mod_info` by GitHub stars among the mods that share one of its
+/// categories and have a star count, itself included. Returns its 1-based
+/// rank (ties share the better rank) and the number of mods ranked, or
+/// `None` if `mod_info` has no star count.
+pub fn star_rank(db: &ModDatabase, mod_info: &ModInfo) -> Option<(usize, usize)> {
+    let stars = mod_info.stars?;
+    let peers: Vec<u32> = db.mods.values()
+        .filter(|other| other.name != mod_info.name)
+        .filter(|other| other.categories.iter().any(|c| mod_info.categories.contains(c)))
+        .filter_map(|other| other.stars)
+        .collect();
+    let ahead = peers.iter().filter(|&&other| other > stars).count();
+    Some((ahead + 1, peers.len() + 1))
+}
+
+/// Returns up to `limit` other mods ranked by the Jaccard similarity of their
+/// description words to `mod_info`'s description. Mods sharing no words are omitted.
+pub fn similar_mods<'a>(db: &'a ModDatabase, mod_info: &ModInfo, limit: usize) -> Vec<(&'a ModInfo, f64)> {
+    let words = description_words(&mod_info.description);
+    if words.is_empty() {
+        return Vec::new();
+    }
+    
+    let mut scored: Vec<(&ModInfo, f64)> = db.mods.values()
+        .filter(|other| other.name != mod_info.name)
+        .filter_map(|other| {
+            let other_words = description_words(&other.description);
+            let shared = words.intersection(&other_words).count();
+            if shared == 0 {
+                return None;
+            }
+            let total = words.union(&other_words).count();
+            Some((other, shared as f64 / total as f64))
+        })
+        .collect();
+    
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+    scored.truncate(limit);
+    scored
+}
+
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "are", "was", "has",
+    "have", "its", "into", "your", "you", "can", "all", "new", "mod", "mods",
+    "adds", "also", "more", "which", "will", "not", "but", "their", "they",
+];
+
+fn description_words(description: &str) -> HashSet<String> {
+    words(description).collect()
+}
+
+/// Splits `text` into lowercase words of three or more characters, skipping
+/// common English and mod-page filler words.
+pub(crate) fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.chars().count() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_rank_counts_only_starred_mods_sharing_a_category() {
+        let mut db = ModDatabase::new();
+        for (name, category, stars) in [
+            ("Cryptid", "Content Mods", Some(900)),
+            ("Bunco", "Content Mods", Some(300)),
+            ("Extra Credit", "Content Mods", None),
+            ("Talisman", "API Mods", Some(1200)),
+        ] {
+            let mod_info = ModInfo { categories: vec![category.to_string()], stars, ..ModInfo::named(name) };
+            db.mods.insert(name.to_string(), mod_info);
+        }
+        
+        assert_eq!(star_rank(&db, &db.mods["Bunco"]), Some((2, 2)));
+        assert_eq!(star_rank(&db, &db.mods["Cryptid"]), Some((1, 2)));
+        assert_eq!(star_rank(&db, &db.mods["Talisman"]), Some((1, 1)));
+        assert_eq!(star_rank(&db, &db.mods["Extra Credit"]), None);
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The user's bookmarked mod names, stored as a JSON array in
+/// `bookmarks.json` next to the mod cache. Favorites are a separate set of
+/// names kept the same way in `favorites.json`.
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    names: BTreeSet<String>,
+}
+
+impl Bookmarks {
+    /// Returns the bookmarks file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("bookmarks.json")
+    }
+
+    /// Returns the favorites file that sits beside `cache_path`.
+    pub fn favorites_path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("favorites.json")
+    }
+
+    /// Loads bookmarks from `path`, or returns an empty set if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let names: Vec<String> = serde_json::from_str(&content)?;
+        Ok(Self { names: names.into_iter().collect() })
+    }
+
+    /// Writes the bookmarks to `path` as a sorted JSON array.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let names: Vec<&String> = self.names.iter().collect();
+        std::fs::write(path, serde_json::to_string_pretty(&names)?)?;
+        Ok(())
+    }
+
+    /// Bookmarks `name`. Returns false if it was already bookmarked (ignoring case).
+    pub fn insert(&mut self, name: &str) -> bool {
+        if self.contains(name) {
+            return false;
+        }
+        self.names.insert(name.to_string())
+    }
+
+    /// Removes `name`, ignoring case. Returns false if it wasn't bookmarked.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.names.len();
+        self.names.retain(|n| !n.eq_ignore_ascii_case(name));
+        self.names.len() != before
+    }
+
+    /// Returns true if `name` is bookmarked, ignoring case.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterates over the bookmarked names in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.names.iter()
+    }
+}
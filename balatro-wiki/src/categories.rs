@@ -0,0 +1,236 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The wiki categories a full update crawls when no categories file exists.
+pub const DEFAULT_CATEGORIES: &[(&str, &str)] = &[
+    ("Content Mods", "Content%20Mods"),
+    ("Joker Mods", "Joker%20Mods"),
+    ("Quality of Life Mods", "Quality%20of%20Life%20Mods"),
+    ("Crossover Mods", "Crossover%20Mods"),
+    ("Technical Mods", "Technical%20Mods"),
+    ("API Mods", "API%20Mods"),
+];
+
+/// Short names the built-in categories can be given by, with the category
+/// each stands for. Resolve names through [`Categories::canonical`].
+const CATEGORY_ALIASES: &[(&str, &str)] = &[
+    ("content", "Content Mods"),
+    ("joker", "Joker Mods"),
+    ("qol", "Quality of Life Mods"),
+    ("crossover", "Crossover Mods"),
+    ("technical", "Technical Mods"),
+    ("api", "API Mods"),
+];
+
+/// A wiki category to crawl: the name mods are filed under in the database,
+/// the URL-encoded category title it is listed from, and optional short
+/// names that can stand in for the name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryDef {
+    pub name: String,
+    pub wiki_category: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl CategoryDef {
+    /// Returns the built-in [`DEFAULT_CATEGORIES`] with their aliases.
+    pub fn defaults() -> Vec<Self> {
+        DEFAULT_CATEGORIES.iter()
+            .map(|(name, wiki_category)| Self {
+                name: name.to_string(),
+                wiki_category: wiki_category.to_string(),
+                aliases: CATEGORY_ALIASES.iter()
+                    .filter(|(_, category)| category == name)
+                    .map(|(alias, _)| alias.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Returns the categories file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("categories.json")
+    }
+
+    /// Loads the JSON array of categories at `path`, or the built-in ones if
+    /// the file doesn't exist. Fails if an entry is missing a field, has an
+    /// empty name or alias, or has a wiki category that isn't URL-safe, or
+    /// if a name or alias is used twice.
+    pub fn load_all(path: &Path) -> Result<Vec<Self>> {
+        if !path.exists() {
+            return Ok(Self::defaults());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let categories: Vec<Self> = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid categories file {}", path.display()))?;
+        validate(&categories).with_context(|| format!("Invalid categories file {}", path.display()))?;
+        Ok(categories)
+    }
+}
+
+fn validate(categories: &[CategoryDef]) -> Result<()> {
+    if categories.is_empty() {
+        return Err(anyhow!("no categories are listed"));
+    }
+    for (index, category) in categories.iter().enumerate() {
+        if category.name.trim().is_empty() {
+            return Err(anyhow!("entry {} has an empty name", index + 1));
+        }
+        if !is_url_safe(&category.wiki_category) {
+            return Err(anyhow!(
+                "'{}' has wiki category '{}', which must be non-empty and URL-encoded (e.g. \"Joker%20Mods\")",
+                category.name, category.wiki_category
+            ));
+        }
+        if categories[..index].iter().any(|earlier| earlier.name == category.name) {
+            return Err(anyhow!("'{}' is listed more than once", category.name));
+        }
+        for alias in &category.aliases {
+            if alias.trim().is_empty() {
+                return Err(anyhow!("'{}' has an empty alias", category.name));
+            }
+            let taken = categories.iter()
+                .flat_map(|other| other.aliases.iter().filter(move |_| other.name != category.name))
+                .chain(categories.iter().map(|other| &other.name))
+                .any(|other| other.eq_ignore_ascii_case(alias));
+            if taken || category.aliases.iter().filter(|a| a.eq_ignore_ascii_case(alias)).count() > 1 {
+                return Err(anyhow!("alias '{}' of '{}' is used more than once", alias, category.name));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Every category known to a run: the name mods are filed under, the wiki
+/// category it is crawled from, and its aliases. Built once from the
+/// categories file so crawling, lookups, and listings agree.
+#[derive(Debug, Clone)]
+pub struct Categories {
+    defs: Vec<CategoryDef>,
+}
+
+impl Default for Categories {
+    fn default() -> Self {
+        Self::new(CategoryDef::defaults())
+    }
+}
+
+impl Categories {
+    pub fn new(defs: Vec<CategoryDef>) -> Self {
+        Self { defs }
+    }
+
+    /// Loads the categories file beside `cache_path` (see [`CategoryDef::load_all`]).
+    pub fn load(cache_path: &Path) -> Result<Self> {
+        CategoryDef::load_all(&CategoryDef::path_for(cache_path)).map(Self::new)
+    }
+
+    /// Iterates over the categories in crawl order.
+    pub fn iter(&self) -> impl Iterator<Item = &CategoryDef> {
+        self.defs.iter()
+    }
+
+    /// Iterates over `(alias, category name)` pairs.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.defs.iter()
+            .flat_map(|def| def.aliases.iter().map(move |alias| (alias.as_str(), def.name.as_str())))
+    }
+
+    /// Returns the wiki category `name` is crawled from, if it is configured.
+    pub fn wiki_category(&self, name: &str) -> Option<&str> {
+        self.defs.iter().find(|def| def.name == name).map(|def| def.wiki_category.as_str())
+    }
+
+    /// Returns the configured category whose alias or name matches `name`
+    /// (ignoring case), or `name` itself when none does.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, category)| category)
+            .or_else(|| self.defs.iter().find(|def| def.name.eq_ignore_ascii_case(name)).map(|def| def.name.as_str()))
+            .unwrap_or(name)
+    }
+}
+
+/// Returns true if `s` is non-empty and made only of unreserved URL
+/// characters and `%XX` escapes.
+fn is_url_safe(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                if !bytes.get(i + 1..i + 3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                    return false;
+                }
+                i += 3;
+            }
+            b if b.is_ascii_alphanumeric() || b"-._~".contains(&b) => i += 1,
+            _ => return false,
+        }
+    }
+    !bytes.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(name: &str, wiki_category: &str) -> CategoryDef {
+        CategoryDef { name: name.to_string(), wiki_category: wiki_category.to_string(), aliases: Vec::new() }
+    }
+
+    #[test]
+    fn entries_need_a_name_and_a_url_safe_wiki_category() {
+        assert!(validate(&CategoryDef::defaults()).is_ok());
+        assert!(validate(&[def("Deck Mods", "Deck%20Mods"), def("Misc", "Misc_2024.v2~")]).is_ok());
+        
+        assert!(validate(&[]).is_err());
+        assert!(validate(&[def(" ", "Deck%20Mods")]).is_err());
+        assert!(validate(&[def("Deck Mods", "")]).is_err());
+        assert!(validate(&[def("Deck Mods", "Deck Mods")]).is_err());
+        assert!(validate(&[def("Deck Mods", "Deck%2")]).is_err());
+        assert!(validate(&[def("Misc", "Misc_(2024)")]).is_err());
+        assert!(validate(&[def("Deck Mods", "Deck%20Mods"), def("Deck Mods", "Decks")]).is_err());
+    }
+
+    #[test]
+    fn aliases_must_be_unique_across_categories() {
+        let with_aliases = |name: &str, aliases: &[&str]| CategoryDef {
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            ..def(name, "Mods")
+        };
+        
+        assert!(validate(&[with_aliases("Deck Mods", &["deck", "decks"]), with_aliases("Misc", &["other"])]).is_ok());
+        assert!(validate(&[with_aliases("Deck Mods", &[""])]).is_err());
+        assert!(validate(&[with_aliases("Deck Mods", &["deck", "Deck"])]).is_err());
+        assert!(validate(&[with_aliases("Deck Mods", &["deck"]), with_aliases("Misc", &["DECK"])]).is_err());
+        assert!(validate(&[with_aliases("Deck Mods", &["misc"]), with_aliases("Misc", &[])]).is_err());
+    }
+
+    #[test]
+    fn aliases_and_names_resolve_to_the_configured_category() {
+        let categories = Categories::default();
+        for (alias, category) in CATEGORY_ALIASES {
+            assert_eq!(categories.canonical(alias), *category);
+            assert_eq!(categories.canonical(&alias.to_uppercase()), *category);
+            assert_eq!(categories.canonical(&category.to_lowercase()), *category);
+        }
+        assert_eq!(categories.canonical("Deck Mods"), "Deck Mods");
+        assert_eq!(categories.wiki_category("Joker Mods"), Some("Joker%20Mods"));
+        
+        let decks = CategoryDef { aliases: vec!["decks".to_string()], ..def("Deck Mods", "Decks") };
+        let custom = Categories::new(vec![decks]);
+        assert_eq!(custom.canonical("DECKS"), "Deck Mods");
+        assert_eq!(custom.canonical("joker"), "joker");
+    }
+
+    #[test]
+    fn missing_file_gives_the_built_in_categories() {
+        let path = std::env::temp_dir().join("balatro-wiki-no-such-dir").join("categories.json");
+        assert_eq!(CategoryDef::load_all(&path).unwrap(), CategoryDef::defaults());
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::database::ModDatabase;
+
+/// What one update changed: mods that appeared, disappeared, or changed
+/// version. Stored as `changes.json` next to the mod cache.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeSet {
+    /// When the update that produced this change set ran (RFC 3339).
+    pub updated_at: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub version_changed: Vec<VersionChange>,
+}
+
+/// A mod whose scraped version differs between two databases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl ChangeSet {
+    /// Diffs `current` against `previous`, with every list sorted by name.
+    pub fn between(previous: &ModDatabase, current: &ModDatabase) -> Self {
+        let mut added: Vec<String> = current.mods.keys()
+            .filter(|name| !previous.mods.contains_key(*name))
+            .cloned()
+            .collect();
+        let mut removed: Vec<String> = previous.mods.keys()
+            .filter(|name| !current.mods.contains_key(*name))
+            .cloned()
+            .collect();
+        let mut version_changed: Vec<VersionChange> = current.mods.values()
+            .filter_map(|mod_info| {
+                let old = previous.mods.get(&mod_info.name)?;
+                (old.version != mod_info.version).then(|| VersionChange {
+                    name: mod_info.name.clone(),
+                    old: old.version.clone(),
+                    new: mod_info.version.clone(),
+                })
+            })
+            .collect();
+        added.sort();
+        removed.sort();
+        version_changed.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        Self { updated_at: Utc::now().to_rfc3339(), added, removed, version_changed }
+    }
+
+    /// Returns true if nothing was added, removed, or re-versioned.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.version_changed.is_empty()
+    }
+
+    /// Returns the change file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("changes.json")
+    }
+
+    /// Loads the last recorded change set, or `None` if no update has recorded one.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Writes the change set to `path`, replacing the previous one.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ModInfo;
+
+    fn database(mods: &[(&str, Option<&str>)]) -> ModDatabase {
+        let mut db = ModDatabase::new();
+        for (name, version) in mods {
+            let mod_info = ModInfo { version: version.map(str::to_string), ..ModInfo::named(name) };
+            db.mods.insert(name.to_string(), mod_info);
+        }
+        db
+    }
+
+    #[test]
+    fn between_reports_added_removed_and_reversioned_mods() {
+        let previous = database(&[("Cryptid", Some("0.5.1")), ("Talisman", Some("2.0.0")), ("Bunco", None)]);
+        let current = database(&[("Cryptid", Some("0.5.2")), ("Talisman", Some("2.0.0")), ("Jen's Almanac", None)]);
+        
+        let changes = ChangeSet::between(&previous, &current);
+        
+        assert_eq!(changes.added, ["Jen's Almanac"]);
+        assert_eq!(changes.removed, ["Bunco"]);
+        assert_eq!(changes.version_changed.len(), 1);
+        let change = &changes.version_changed[0];
+        assert_eq!(change.name, "Cryptid");
+        assert_eq!(change.old.as_deref(), Some("0.5.1"));
+        assert_eq!(change.new.as_deref(), Some("0.5.2"));
+        assert!(ChangeSet::between(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,675 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use crate::categories::Categories;
+use crate::scraper::{WikiScraper, DEFAULT_CHECKPOINT_INTERVAL};
+use crate::search::SearchText;
+use crate::text::normalize_name;
+use crate::CACHE_FILE;
+
+/// Everything known about a single mod, as scraped from its wiki page.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ModInfo {
+    pub name: String,
+    pub description: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub github_url: Option<String>,
+    /// Download links from the infobox or a "Download" section: GameBanana,
+    /// Google Drive, and Nexus Mods pages, and any direct `.zip`, including
+    /// GitHub release and archive zips.
+    #[serde(default)]
+    pub download_urls: Vec<String>,
+    pub wiki_url: String,
+    /// Absolute URL of the infobox cover image, if the page has one.
+    #[serde(default)]
+    pub image_url: Option<String>,
+    /// Every category the mod is listed under. Older caches stored a single
+    /// `category` string, which is read as a one-element list.
+    #[serde(alias = "category", deserialize_with = "one_or_many")]
+    pub categories: Vec<String>,
+    /// More specific labels from the infobox's "Type" or "Tags" row, e.g.
+    /// "Joker" or "Deck".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub dependencies: Vec<String>,
+    /// Feature list items from the mod page, e.g. "Adds 20 new Jokers".
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// When this mod's scraped data last changed (RFC 3339). `None` for
+    /// caches written before the field existed.
+    #[serde(default)]
+    pub last_updated: Option<String>,
+    /// When the mod's wiki page was last edited (RFC 3339), from the page's
+    /// latest revision. `None` until `update` has fetched it.
+    #[serde(default)]
+    pub last_edited: Option<String>,
+    /// Whether the mod's GitHub repository has published any Releases.
+    /// `None` when it has no GitHub URL or hasn't been checked yet.
+    #[serde(default)]
+    pub has_github_releases: Option<bool>,
+    /// When `has_github_releases` was last looked up (RFC 3339). A `false`
+    /// answer is looked up again once it is older than
+    /// [`RELEASES_RECHECK_AFTER`].
+    #[serde(default)]
+    pub releases_checked: Option<String>,
+    /// Votes from the wiki's kudos/voting widget, if the page shows one.
+    #[serde(default)]
+    pub kudos_count: Option<u32>,
+    /// Stargazer count of the mod's GitHub repository, fetched by
+    /// `update --github-stars`. `None` when it hasn't been fetched.
+    #[serde(default)]
+    pub stars: Option<u32>,
+}
+
+impl ModInfo {
+    /// The JSON keys a serialized `ModInfo` has, in declaration order.
+    pub const FIELD_NAMES: &'static [&'static str] = &[
+        "name",
+        "description",
+        "author",
+        "version",
+        "github_url",
+        "download_urls",
+        "wiki_url",
+        "image_url",
+        "categories",
+        "tags",
+        "dependencies",
+        "features",
+        "last_updated",
+        "last_edited",
+        "has_github_releases",
+        "releases_checked",
+        "kudos_count",
+        "stars",
+    ];
+
+    /// A mod with the given name and every other field empty, for building
+    /// one by hand with struct update syntax.
+    pub fn named(name: &str) -> Self {
+        Self { name: name.to_string(), ..Self::default() }
+    }
+
+    /// The mod's categories joined for display, or `"Unknown"` if it has none.
+    pub fn category_label(&self) -> String {
+        if self.categories.is_empty() {
+            "Unknown".to_string()
+        } else {
+            self.categories.join(", ")
+        }
+    }
+
+    /// Returns true if the mod links to GitHub and whether its repository
+    /// has Releases is unknown, or was `false` more than
+    /// [`RELEASES_RECHECK_AFTER`] ago.
+    pub fn releases_check_due(&self) -> bool {
+        if self.github_url.is_none() {
+            return false;
+        }
+        match self.has_github_releases {
+            None => true,
+            Some(true) => false,
+            Some(false) => self.releases_checked.as_deref()
+                .and_then(|checked| DateTime::parse_from_rfc3339(checked).ok())
+                .is_none_or(|checked| Utc::now().signed_duration_since(checked) >= RELEASES_RECHECK_AFTER),
+        }
+    }
+
+    /// Returns true if the mod is listed under `category`, ignoring case.
+    pub fn in_category(&self, category: &str) -> bool {
+        self.categories.iter().any(|c| c.eq_ignore_ascii_case(category))
+    }
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(category) => vec![category],
+        OneOrMany::Many(categories) => categories,
+    })
+}
+
+/// The local mod cache: every scraped mod keyed by name, plus the mod names
+/// listed under each category.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModDatabase {
+    /// Format version the database was written in; caches from before the
+    /// field existed are version 1. See [`SCHEMA_VERSION`].
+    #[serde(default = "first_schema_version")]
+    pub schema_version: u32,
+    pub mods: HashMap<String, ModInfo>,
+    pub categories: HashMap<String, Vec<String>>,
+    pub last_updated: String,
+    /// When each category's member list was last scraped (RFC 3339).
+    /// Categories missing here fall back to `last_updated`.
+    #[serde(default)]
+    pub category_updated: HashMap<String, String>,
+    /// Lowercased search text per mod name, built when the database is
+    /// loaded (or on the first search) and never persisted. Call
+    /// [`ModDatabase::clear_search_index`] after changing `mods`.
+    #[serde(skip)]
+    search_index: OnceLock<HashMap<String, SearchText>>,
+}
+
+/// Cache format version written by this build. Bump it, and add a step to
+/// `MIGRATIONS`, whenever the cache format changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One step of [`migrate`]: upgrades a cache's JSON in place from the
+/// version at its index plus one to the next version.
+type Migration = fn(&mut serde_json::Value) -> Result<()>;
+
+const MIGRATIONS: &[Migration] = &[];
+
+const _: () = assert!(MIGRATIONS.len() + 1 == SCHEMA_VERSION as usize, "one migration per schema version bump");
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+/// Upgrades a database parsed as JSON from its `schema_version` (1 when
+/// missing) to [`SCHEMA_VERSION`], one migration at a time. Fails if it was
+/// written by a newer build or a migration step fails.
+pub(crate) fn migrate(mut value: serde_json::Value) -> Result<serde_json::Value> {
+    let version = match value.get("schema_version") {
+        None => 1,
+        Some(version) => version.as_u64()
+            .filter(|&v| v >= 1)
+            .ok_or_else(|| anyhow!("invalid schema_version {}", version))?,
+    };
+    if version > SCHEMA_VERSION as u64 {
+        return Err(anyhow!(
+            "written in format version {}, newer than version {} this build reads",
+            version, SCHEMA_VERSION
+        ));
+    }
+    for step in &MIGRATIONS[version as usize - 1..] {
+        step(&mut value)?;
+    }
+    let Some(object) = value.as_object_mut() else {
+        return Err(anyhow!("not a JSON object"));
+    };
+    object.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    Ok(value)
+}
+
+/// How old a category listing may get before read commands refresh it.
+pub const DEFAULT_MAX_AGE: Duration = Duration::hours(24);
+
+/// How long a repository found without Releases is trusted before it is
+/// checked again.
+pub const RELEASES_RECHECK_AFTER: Duration = Duration::days(7);
+
+/// Returns the cache file for `profile`, or the main cache when `profile` is `None`.
+/// Named profiles live under `~/.cache/balatro-wiki/profiles/<name>/mods.json`.
+pub fn cache_path(profile: Option<&str>) -> Result<PathBuf> {
+    let main_cache = PathBuf::from(shellexpand::tilde(CACHE_FILE).as_ref());
+    let Some(profile) = profile else {
+        return Ok(main_cache);
+    };
+    
+    if profile.is_empty() || !profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow!("Invalid profile name '{}': use only letters, digits, '-' and '_'", profile));
+    }
+    
+    let cache_dir = main_cache.parent().unwrap_or(Path::new("."));
+    let file_name = main_cache.file_name().unwrap_or("mods.json".as_ref());
+    Ok(cache_dir.join("profiles").join(profile).join(file_name))
+}
+
+impl Default for ModDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModDatabase {
+    /// Creates an empty database stamped with the current time.
+    pub fn new() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            mods: HashMap::new(),
+            categories: HashMap::new(),
+            last_updated: Utc::now().to_rfc3339(),
+            category_updated: HashMap::new(),
+            search_index: OnceLock::new(),
+        }
+    }
+
+    /// Returns the search index, building it from the current mods if it
+    /// hasn't been built since the database was loaded or last changed.
+    pub(crate) fn search_index(&self) -> &HashMap<String, SearchText> {
+        self.search_index.get_or_init(|| {
+            self.mods.iter().map(|(name, mod_info)| (name.clone(), SearchText::new(mod_info))).collect()
+        })
+    }
+
+    /// Drops the search index so the next search rebuilds it from `mods`.
+    pub fn clear_search_index(&mut self) {
+        self.search_index = OnceLock::new();
+    }
+
+    /// Loads the main cached database, or returns an empty one if there is no cache yet.
+    pub fn load_or_create() -> Result<Self> {
+        Self::load_from(&cache_path(None)?)
+    }
+
+    /// Parses a database serialized as JSON, upgrading it first if it was
+    /// written in an older format (see [`SCHEMA_VERSION`]), and builds its
+    /// search index.
+    pub fn from_json(content: &str) -> Result<Self> {
+        let value = migrate(serde_json::from_str(content)?)?;
+        let db: Self = serde_json::from_value(value)?;
+        db.search_index();
+        Ok(db)
+    }
+
+    /// Returns the checkpoint file a full update saves its progress to
+    /// (see [`WikiScraper::with_checkpoints`]), beside `cache_path`.
+    pub fn partial_path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("mods.partial.json")
+    }
+
+    /// Removes the checkpoint left beside `cache_path`, once the finished
+    /// update has been saved.
+    pub fn discard_partial(cache_path: &Path) {
+        let _ = std::fs::remove_file(Self::partial_path_for(cache_path));
+    }
+
+    /// Loads the database cached at `cache_path`, upgrading an older format,
+    /// or returns an empty one if the file doesn't exist yet. Without a cache,
+    /// the checkpoint of an interrupted first update is loaded instead; it is
+    /// dated as never updated, so the next freshness check finishes the job.
+    /// A cache that can't be parsed or upgraded is copied to `mods.json.bak`
+    /// and treated as empty, so the next refresh rebuilds it.
+    pub fn load_from(cache_path: &Path) -> Result<Self> {
+        if !cache_path.exists() {
+            let partial = std::fs::read_to_string(Self::partial_path_for(cache_path)).ok();
+            return Ok(partial.and_then(|content| Self::from_json(&content).ok()).unwrap_or_default());
+        }
+        
+        let content = std::fs::read_to_string(cache_path)?;
+        match Self::from_json(&content) {
+            Ok(db) => Ok(db),
+            Err(e) => {
+                let backup = cache_path.with_extension("json.bak");
+                eprintln!("⚠️  Cache {} can't be read ({}); ignoring it", cache_path.display(), e);
+                match std::fs::copy(cache_path, &backup) {
+                    Ok(_) => eprintln!("   The unreadable file was backed up to {}", backup.display()),
+                    Err(e) => eprintln!("   Could not back it up to {}: {}", backup.display(), e),
+                }
+                Ok(Self::new())
+            }
+        }
+    }
+
+    /// Loads the cached database without ever touching the network, warning
+    /// when there is nothing cached.
+    pub fn load_offline(cache_path: &Path) -> Result<Self> {
+        let db = Self::load_from(cache_path)?;
+        if db.mods.is_empty() {
+            eprintln!("⚠️  No cached mod database found; run 'balatro-wiki update' while online");
+        }
+        Ok(db)
+    }
+
+    /// Looks up a mod by name, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&ModInfo> {
+        let name_lower = name.to_lowercase();
+        self.mods.values().find(|m| m.name.to_lowercase() == name_lower)
+    }
+
+    /// Looks up a mod by name ignoring case, falling back to comparing
+    /// [`normalize_name`] forms so `"cryptid"` finds `"Cryptid (Content Mod)"`.
+    /// A normalized name shared by several mods matches none of them.
+    pub fn find_normalized(&self, name: &str) -> Option<&ModInfo> {
+        if let Some(mod_info) = self.find(name) {
+            return Some(mod_info);
+        }
+        
+        let target = normalize_name(name);
+        if target.is_empty() {
+            return None;
+        }
+        let mut matches = self.mods.values().filter(|m| normalize_name(&m.name) == target);
+        match (matches.next(), matches.next()) {
+            (Some(only), None) => Some(only),
+            _ => None,
+        }
+    }
+
+    /// Looks up a mod by name like [`ModDatabase::find_normalized`], falling
+    /// back to a unique partial match so abbreviations like `"stea"` find
+    /// `Steamodded`. Prefix matches win over matches elsewhere in the name.
+    pub fn resolve(&self, name: &str) -> Option<&ModInfo> {
+        if let Some(mod_info) = self.find_normalized(name) {
+            return Some(mod_info);
+        }
+        
+        let name_lower = name.to_lowercase();
+        let partial: Vec<&ModInfo> = self.mods.values()
+            .filter(|m| m.name.to_lowercase().contains(&name_lower))
+            .collect();
+        let prefixed: Vec<&ModInfo> = partial.iter()
+            .copied()
+            .filter(|m| m.name.to_lowercase().starts_with(&name_lower))
+            .collect();
+        
+        match (prefixed.as_slice(), partial.as_slice()) {
+            ([only], _) => Some(only),
+            ([], [only]) => Some(only),
+            _ => None,
+        }
+    }
+
+    /// Returns the category key matching `name` exactly, by one of
+    /// `categories`' aliases (e.g. `"joker"`), or ignoring case.
+    pub fn resolve_category(&self, categories: &Categories, name: &str) -> Option<&str> {
+        if let Some((key, _)) = self.categories.get_key_value(name) {
+            return Some(key);
+        }
+        let target = categories.canonical(name);
+        self.categories.keys()
+            .find(|key| key.eq_ignore_ascii_case(target))
+            .map(|key| key.as_str())
+    }
+
+    /// Returns the known category closest to `name` by edit distance, if
+    /// any is close enough to be a plausible typo.
+    pub fn suggest_category(&self, categories: &Categories, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        let aliases = categories.aliases()
+            .filter_map(|(alias, category)| self.resolve_category(categories, category).map(|key| (alias, key)));
+        self.categories.keys()
+            .map(|key| (key.as_str(), key.as_str()))
+            .chain(aliases)
+            .map(|(candidate, key)| (strsim::levenshtein(&name, &candidate.to_lowercase()), candidate, key))
+            .filter(|(distance, candidate, _)| *distance <= (candidate.len() / 3).max(2))
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, _, key)| key)
+    }
+
+    /// Returns up to `limit` mods whose names are within typo distance of
+    /// `query`, closest first.
+    pub fn suggest_mods(&self, query: &str, limit: usize) -> Vec<&ModInfo> {
+        let query = query.to_lowercase();
+        let mut close: Vec<(usize, &ModInfo)> = self.mods.values()
+            .map(|m| (strsim::levenshtein(&query, &m.name.to_lowercase()), m))
+            .filter(|(distance, m)| *distance <= (m.name.chars().count() / 3).max(2))
+            .collect();
+        close.sort_by(|(a, a_mod), (b, b_mod)| a.cmp(b).then_with(|| a_mod.name.cmp(&b_mod.name)));
+        close.into_iter().take(limit).map(|(_, m)| m).collect()
+    }
+
+    /// Keeps `previous`'s per-mod `last_updated` for mods whose scraped data
+    /// is unchanged, so the timestamp reflects real changes rather than the
+    /// time of the last crawl. GitHub Release info and page edit times that
+    /// weren't fetched this time are also kept from `previous`.
+    pub fn carry_over_timestamps(&mut self, previous: &ModDatabase) {
+        for (name, mod_info) in self.mods.iter_mut() {
+            let Some(old) = previous.mods.get(name) else {
+                continue;
+            };
+            if mod_info.github_url == old.github_url {
+                if mod_info.has_github_releases.is_none() {
+                    mod_info.has_github_releases = old.has_github_releases;
+                    mod_info.releases_checked = old.releases_checked.clone();
+                }
+                if mod_info.stars.is_none() {
+                    mod_info.stars = old.stars;
+                }
+            }
+            if mod_info.last_edited.is_none() {
+                mod_info.last_edited = old.last_edited.clone();
+            }
+            let unchanged = ModInfo {
+                last_updated: old.last_updated.clone(),
+                last_edited: old.last_edited.clone(),
+                releases_checked: old.releases_checked.clone(),
+                ..mod_info.clone()
+            };
+            if old.last_updated.is_some() && unchanged == *old {
+                mod_info.last_updated = old.last_updated.clone();
+            }
+        }
+    }
+
+    /// Returns true when the database is empty or any category was last
+    /// scraped at least `max_age` ago.
+    pub fn should_update(&self, max_age: Duration) -> bool {
+        self.mods.is_empty() || !self.stale_categories(max_age).is_empty()
+    }
+
+    /// Returns the categories last scraped at least `max_age` ago (or with
+    /// an unreadable timestamp). With no categories at all, the whole
+    /// database counts as one unnamed category.
+    pub fn stale_categories(&self, max_age: Duration) -> Vec<String> {
+        if self.categories.is_empty() {
+            return if is_stale(&self.last_updated, max_age) { vec![String::new()] } else { Vec::new() };
+        }
+        
+        let mut stale: Vec<String> = self.categories.keys()
+            .filter(|category| {
+                let updated = self.category_updated.get(*category).unwrap_or(&self.last_updated);
+                is_stale(updated, max_age)
+            })
+            .cloned()
+            .collect();
+        stale.sort();
+        stale
+    }
+
+    /// Loads the cache, refreshing it from the wiki first if it is older
+    /// than [`DEFAULT_MAX_AGE`].
+    pub async fn ensure_fresh_silent(scraper: &WikiScraper, cache_path: &Path) -> Result<Self> {
+        Self::ensure_fresh_with_verbosity(scraper, cache_path, DEFAULT_MAX_AGE, false).await
+    }
+    
+    /// Like [`ModDatabase::ensure_fresh_silent`], with categories older than
+    /// `max_age` counting as stale, optionally logging progress to stdout.
+    pub async fn ensure_fresh_with_verbosity(
+        scraper: &WikiScraper,
+        cache_path: &Path,
+        max_age: Duration,
+        verbose: bool,
+    ) -> Result<Self> {
+        let mut db = Self::load_from(cache_path)?;
+        
+        let stale = db.stale_categories(max_age);
+        if db.mods.is_empty() || (!stale.is_empty() && stale.len() >= db.categories.len()) {
+            if verbose {
+                println!("🔄 Updating mod database...");
+            }
+            let previous = Arc::new(db);
+            db = scraper.clone()
+                .with_checkpoints(cache_path, DEFAULT_CHECKPOINT_INTERVAL)
+                .with_previous(previous.clone())
+                .update_database_with_verbosity(verbose)
+                .await?;
+            db.carry_over_timestamps(&previous);
+            db.save_to(cache_path)?;
+            Self::discard_partial(cache_path);
+            if verbose {
+                println!("✅ Database updated with {} mods", db.mods.len());
+            }
+        } else if !stale.is_empty() {
+            // Only some categories are stale: refresh just those
+            for category in &stale {
+                if verbose {
+                    println!("🔄 Updating category {}...", category);
+                }
+                scraper.update_category(&mut db, category, verbose).await?;
+            }
+            db.save_to(cache_path)?;
+        }
+        
+        Ok(db)
+    }
+
+    /// Writes the database to the main cache file.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&cache_path(None)?)
+    }
+
+    /// Writes the database to `cache_path`, creating its directory if needed.
+    /// The data goes to a temporary file beside it that is then renamed over
+    /// `cache_path`, so readers never see a partially written cache.
+    pub fn save_to(&self, cache_path: &Path) -> Result<()> {
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        write_atomically(cache_path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+}
+
+/// Writes `content` to a temporary file beside `path` and renames it over
+/// `path`, so readers see either the old file or the whole new one.
+pub(crate) fn write_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".tmp.{}", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    
+    let written = write_synced(&tmp_path, content)
+        .and_then(|()| std::fs::rename(&tmp_path, path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Writes `content` to `path` and flushes it to disk before returning.
+fn write_synced(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+/// Returns true if the RFC 3339 `timestamp` is at least `max_age` old or invalid.
+fn is_stale(timestamp: &str, max_age: Duration) -> bool {
+    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+        Ok(updated) => Utc::now().signed_duration_since(updated.with_timezone(&Utc)) >= max_age,
+        Err(_) => true, // Invalid timestamp, force update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_names_match_serialized_keys() {
+        let mod_info: ModInfo = serde_json::from_value(serde_json::json!({
+            "name": "Cryptid",
+            "description": "Adds many new Jokers.",
+            "author": null,
+            "version": null,
+            "github_url": null,
+            "wiki_url": "https://balatromods.miraheze.org/wiki/Cryptid",
+            "categories": [],
+            "dependencies": [],
+        }))
+        .unwrap();
+        let value = serde_json::to_value(&mod_info).unwrap();
+        let keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, ModInfo::FIELD_NAMES);
+    }
+
+    #[test]
+    fn every_category_alias_resolves() {
+        let categories = Categories::default();
+        let mut db = ModDatabase::new();
+        for (_, category) in categories.aliases() {
+            db.categories.insert(category.to_string(), Vec::new());
+        }
+        
+        for (alias, category) in [
+            ("content", "Content Mods"),
+            ("joker", "Joker Mods"),
+            ("qol", "Quality of Life Mods"),
+            ("crossover", "Crossover Mods"),
+            ("technical", "Technical Mods"),
+            ("api", "API Mods"),
+        ] {
+            assert_eq!(db.resolve_category(&categories, alias), Some(category));
+            assert_eq!(db.resolve_category(&categories, &category.to_lowercase()), Some(category));
+        }
+        assert_eq!(db.resolve_category(&categories, "nonsense"), None);
+    }
+
+    fn db_of(names: &[&str]) -> ModDatabase {
+        let mut db = ModDatabase::new();
+        for name in names {
+            db.mods.insert(name.to_string(), ModInfo::named(name));
+        }
+        db
+    }
+
+    #[test]
+    fn suggestions_are_close_names_only() {
+        let db = db_of(&["Cryptid", "Talisman", "Steamodded", "Jen's Almanac", "Cryptic"]);
+        
+        let names = |query| db.suggest_mods(query, 3).iter().map(|m| m.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names("cryptix"), vec!["Cryptic", "Cryptid"]);
+        assert_eq!(names("crytpid"), vec!["Cryptid"]);
+        assert_eq!(names("talsman"), vec!["Talisman"]);
+        assert!(names("balatro").is_empty());
+    }
+
+    #[test]
+    fn normalized_lookup_needs_a_unique_match() {
+        let db = db_of(&["Cryptid (Content Mod)", "Joker Pack (Beta)", "Joker-Pack", "Jen's Almanac"]);
+        let found = |name| db.find_normalized(name).map(|m| m.name.as_str());
+        
+        assert_eq!(found("cryptid"), Some("Cryptid (Content Mod)"));
+        assert_eq!(found("jens almanac"), Some("Jen's Almanac"));
+        assert_eq!(found("Joker-Pack"), Some("Joker-Pack"));
+        assert_eq!(found("joker pack"), None);
+        assert_eq!(found("(mod)"), None);
+    }
+
+    #[test]
+    fn only_unknown_or_stale_negative_release_checks_are_due() {
+        let db = db_of(&["Cryptid"]);
+        let mut mod_info = db.mods["Cryptid"].clone();
+        assert!(!mod_info.releases_check_due());
+        
+        mod_info.github_url = Some("https://github.com/MathIsFun0/Cryptid".to_string());
+        assert!(mod_info.releases_check_due());
+        mod_info.has_github_releases = Some(true);
+        assert!(!mod_info.releases_check_due());
+        mod_info.has_github_releases = Some(false);
+        assert!(mod_info.releases_check_due());
+        mod_info.releases_checked = Some(Utc::now().to_rfc3339());
+        assert!(!mod_info.releases_check_due());
+        mod_info.releases_checked = Some((Utc::now() - RELEASES_RECHECK_AFTER).to_rfc3339());
+        assert!(mod_info.releases_check_due());
+    }
+
+    #[test]
+    fn caches_without_a_version_are_upgraded_and_newer_ones_refused() {
+        let legacy = serde_json::json!({ "mods": {}, "categories": {}, "last_updated": "2024-03-01T00:00:00Z" });
+        let db = ModDatabase::from_json(&legacy.to_string()).unwrap();
+        assert_eq!(db.schema_version, SCHEMA_VERSION);
+        assert_eq!(db.last_updated, "2024-03-01T00:00:00Z");
+        
+        let mut newer = legacy.clone();
+        newer["schema_version"] = (SCHEMA_VERSION + 1).into();
+        assert!(ModDatabase::from_json(&newer.to_string()).is_err());
+        newer["schema_version"] = 0.into();
+        assert!(ModDatabase::from_json(&newer.to_string()).is_err());
+    }
+}
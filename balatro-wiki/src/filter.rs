@@ -0,0 +1,48 @@
+use anyhow::{anyhow, Result};
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, Native, RcIter};
+use jaq_json::Val;
+
+use crate::database::ModInfo;
+
+/// A compiled jq expression used as a boolean predicate over mods.
+pub struct JsonFilter {
+    filter: jaq_core::Filter<Native<Val>>,
+}
+
+impl JsonFilter {
+    /// Compiles `expr` with the jq standard library available.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let program = File { code: expr, path: () };
+        let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+        let arena = Arena::default();
+
+        let modules = loader
+            .load(&arena, program)
+            .map_err(|errs| invalid_expression(expr, errs))?;
+        let filter = Compiler::default()
+            .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+            .compile(modules)
+            .map_err(|errs| invalid_expression(expr, errs))?;
+        Ok(Self { filter })
+    }
+
+    /// Returns true if the expression's first output for `mod_info`, serialized
+    /// as it is in the cache, is exactly `true`.
+    pub fn matches(&self, mod_info: &ModInfo) -> Result<bool> {
+        let input = Val::from(serde_json::to_value(mod_info)?);
+        let inputs = RcIter::new(core::iter::empty());
+        let mut outputs = self.filter.run((Ctx::new([], &inputs), input));
+
+        match outputs.next() {
+            Some(Ok(value)) => Ok(value == Val::Bool(true)),
+            Some(Err(e)) => Err(anyhow!("jq filter failed on '{}': {}", mod_info.name, e)),
+            None => Ok(false),
+        }
+    }
+}
+
+fn invalid_expression<F, E: std::fmt::Debug>(expr: &str, errs: Vec<(F, E)>) -> anyhow::Error {
+    let errs: Vec<E> = errs.into_iter().map(|(_, e)| e).collect();
+    anyhow!("Invalid jq expression '{}': {:?}", expr, errs)
+}
@@ -0,0 +1,83 @@
+/// Base URL of the GitHub REST API.
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// First path segments of github.com pages that aren't user or organization
+/// accounts, so `github.com/<segment>/<name>` is never a repository.
+const RESERVED_OWNERS: &[&str] = &[
+    "about", "apps", "collections", "features", "login", "marketplace", "orgs", "settings", "sponsors", "topics",
+];
+
+/// Returns the `owner/repo` path of a GitHub repository URL, ignoring any
+/// trailing path (e.g. `/tree/main`) or `.git` suffix.
+pub fn repo_path(github_url: &str) -> Option<String> {
+    let rest = github_url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+    let path = rest.strip_prefix("github.com/")?;
+
+    let mut segments = path.split(['/', '?', '#']).filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    if repo.is_empty() || RESERVED_OWNERS.contains(&owner.to_lowercase().as_str()) {
+        return None;
+    }
+    Some(format!("{}/{}", owner, repo))
+}
+
+/// Reduces a link into a GitHub repository (a release asset, a file under
+/// `/blob/...`, etc.) to the repository root, `https://github.com/owner/repo`.
+/// Returns `None` for links that aren't into a repository, such as user pages or gists.
+pub fn repo_url(github_url: &str) -> Option<String> {
+    repo_path(github_url).map(|path| format!("https://github.com/{}", path))
+}
+
+/// Picks a mod's GitHub URL from the GitHub links on its page: the first link
+/// into a repository, reduced to the repository root, or else the first link as-is.
+pub fn best_repo_url<'a>(links: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut first = None;
+    for link in links {
+        if let Some(url) = repo_url(link) {
+            return Some(url);
+        }
+        first.get_or_insert(link);
+    }
+    first.map(|link| link.to_string())
+}
+
+/// The `GITHUB_TOKEN` environment variable, if set, used to raise the
+/// unauthenticated API rate limit.
+pub(crate) fn token() -> Option<String> {
+    std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_url_drops_deep_paths_queries_and_trailing_slashes() {
+        let root = Some("https://github.com/MathIsFun0/Cryptid".to_string());
+        assert_eq!(repo_url("https://github.com/MathIsFun0/Cryptid/releases/download/v0.5.2/Cryptid.zip"), root);
+        assert_eq!(repo_url("https://github.com/MathIsFun0/Cryptid/blob/main/README.md"), root);
+        assert_eq!(repo_url("http://www.github.com/MathIsFun0/Cryptid/?tab=readme"), root);
+        assert_eq!(repo_url("https://github.com/MathIsFun0/Cryptid.git"), root);
+        assert_eq!(repo_url("https://github.com/MathIsFun0/Cryptid/"), root);
+    }
+
+    #[test]
+    fn repo_url_rejects_non_repository_links() {
+        assert_eq!(repo_url("https://github.com/MathIsFun0"), None);
+        assert_eq!(repo_url("https://gist.github.com/someone/abc123"), None);
+        assert_eq!(repo_url("https://github.com/sponsors/MathIsFun0"), None);
+    }
+
+    #[test]
+    fn best_repo_url_prefers_repository_links() {
+        let links = ["https://github.com/MathIsFun0", "https://github.com/MathIsFun0/Cryptid/tree/main"];
+        assert_eq!(best_repo_url(links), Some("https://github.com/MathIsFun0/Cryptid".to_string()));
+        assert_eq!(best_repo_url(["https://github.com/MathIsFun0"]), Some("https://github.com/MathIsFun0".to_string()));
+        assert_eq!(best_repo_url([]), None);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::database::write_atomically;
+
+/// A fetched page body with the validators the server sent for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Page bodies keyed by URL, stored in `http_cache.json` next to the mod
+/// cache, so a refresh can send conditional requests and reuse the body
+/// when the wiki answers `304 Not Modified`.
+#[derive(Debug, Default)]
+pub struct HttpCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+    /// URLs looked up or stored since the cache was loaded or last pruned.
+    used: Mutex<HashSet<String>>,
+}
+
+impl HttpCache {
+    /// Returns the HTTP cache file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("http_cache.json")
+    }
+
+    /// Loads the cache from `path`. A missing or unreadable file gives an
+    /// empty cache, since every entry can be refetched.
+    pub fn load(path: &Path) -> Self {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { entries: Mutex::new(entries), used: Mutex::default() }
+    }
+
+    /// Writes the cache to `path`, replacing the old file in one step so an
+    /// interrupted save leaves the previous cache intact.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(&*self.entries.lock().unwrap())?;
+        write_atomically(path, content.as_bytes())
+    }
+
+    /// Drops every entry that hasn't been looked up or stored since the
+    /// cache was loaded or last pruned, such as pages of mods that left the
+    /// wiki. Meant for after a crawl of the whole wiki; pages it skipped,
+    /// e.g. because they were unchanged, lose their entries too.
+    pub fn prune_unused(&self) {
+        let mut used = self.used.lock().unwrap();
+        self.entries.lock().unwrap().retain(|url, _| used.contains(url));
+        used.clear();
+    }
+
+    /// Returns the cached response for `url`, if any.
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        self.used.lock().unwrap().insert(url.to_string());
+        self.entries.lock().unwrap().get(url).cloned()
+    }
+
+    /// Stores `response` as the latest body of `url`.
+    pub fn insert(&self, url: &str, response: CachedResponse) {
+        self.used.lock().unwrap().insert(url.to_string());
+        self.entries.lock().unwrap().insert(url.to_string(), response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pruning_keeps_only_the_entries_used_since_loading() {
+        let path = std::env::temp_dir().join(format!("balatro-wiki-http-cache-{}.json", std::process::id()));
+        let response = |body: &str| CachedResponse {
+            etag: Some("\"1\"".to_string()),
+            last_modified: None,
+            body: body.to_string(),
+        };
+        let cache = HttpCache::default();
+        cache.insert("https://wiki/Cryptid", response("cryptid"));
+        cache.insert("https://wiki/Gone", response("gone"));
+        cache.save(&path).unwrap();
+        
+        let reloaded = HttpCache::load(&path);
+        assert!(reloaded.get("https://wiki/Cryptid").is_some());
+        reloaded.insert("https://wiki/Talisman", response("talisman"));
+        reloaded.prune_unused();
+        reloaded.save(&path).unwrap();
+        let pruned = HttpCache::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        
+        assert_eq!(pruned.get("https://wiki/Cryptid").unwrap().body, "cryptid");
+        assert_eq!(pruned.get("https://wiki/Talisman").unwrap().body, "talisman");
+        assert!(pruned.get("https://wiki/Gone").is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::database::ModInfo;
+use crate::github;
+
+/// A mod installed through the `install` command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledMod {
+    pub name: String,
+    pub github_url: Option<String>,
+    /// The wiki's version of the mod at install time.
+    pub version: Option<String>,
+    /// Directory the installer cloned into under the game's mods folder,
+    /// i.e. the repository name.
+    pub directory: Option<String>,
+    /// When the mod was installed (RFC 3339).
+    pub installed_at: String,
+}
+
+impl InstalledMod {
+    /// Describes `mod_info` as installed now.
+    pub fn from_mod(mod_info: &ModInfo) -> Self {
+        let directory = mod_info.github_url.as_deref()
+            .and_then(github::repo_path)
+            .and_then(|path| path.split('/').nth(1).map(|repo| repo.to_string()));
+        Self {
+            name: mod_info.name.clone(),
+            github_url: mod_info.github_url.clone(),
+            version: mod_info.version.clone(),
+            directory,
+            installed_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// The manifest of installed mods, stored as a JSON array in
+/// `installed.json` next to the mod cache.
+#[derive(Debug, Clone, Default)]
+pub struct InstalledMods {
+    mods: Vec<InstalledMod>,
+}
+
+impl InstalledMods {
+    /// Returns the manifest file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("installed.json")
+    }
+
+    /// Loads the manifest from `path`, or returns an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self { mods: serde_json::from_str(&content)? })
+    }
+
+    /// Writes the manifest to `path`, sorted by name.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.mods)?)?;
+        Ok(())
+    }
+
+    /// Records `installed`, replacing any earlier entry for the same mod.
+    pub fn record(&mut self, installed: InstalledMod) {
+        self.mods.retain(|m| !m.name.eq_ignore_ascii_case(&installed.name));
+        self.mods.push(installed);
+        self.mods.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Removes the entry for `name` (ignoring case) and returns it, if there was one.
+    pub fn remove(&mut self, name: &str) -> Option<InstalledMod> {
+        let index = self.mods.iter().position(|m| m.name.eq_ignore_ascii_case(name))?;
+        Some(self.mods.remove(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &InstalledMod> {
+        self.mods.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty()
+    }
+}
@@ -0,0 +1,58 @@
+//! Scraping, caching, and searching of the Balatro mod wiki.
+//!
+//! The `balatro-wiki` binary is a thin CLI over this crate. Other tools can
+//! use [`WikiScraper`] to crawl the wiki, [`ModDatabase`] to load and persist
+//! the local cache, and [`search`] to rank mods the same way the `search`
+//! command does.
+
+mod analysis;
+mod bookmarks;
+mod categories;
+mod changes;
+mod database;
+mod filter;
+mod github;
+mod http_cache;
+mod installed;
+mod rate_limit;
+mod recent;
+mod scraper;
+mod search;
+mod selectors;
+mod sitemap;
+mod snapshot;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod text;
+mod tfidf;
+
+pub use analysis::{dependency_tree, dependents, similar_mods, star_rank, DependencyNode};
+pub use bookmarks::Bookmarks;
+pub use categories::{Categories, CategoryDef, DEFAULT_CATEGORIES};
+pub use changes::{ChangeSet, VersionChange};
+pub use database::{cache_path, ModDatabase, ModInfo, DEFAULT_MAX_AGE, RELEASES_RECHECK_AFTER, SCHEMA_VERSION};
+pub use filter::JsonFilter;
+pub use http_cache::{CachedResponse, HttpCache};
+pub use installed::{InstalledMod, InstalledMods};
+pub use rate_limit::TokenBucket;
+pub use recent::{RecentMods, RECENT_CACHE_TTL};
+pub use scraper::{
+    LinkStatus, NotAModPage, ScraperConfig, UpdateFailures, UpdateProgress, UpdateTimings, WikiScraper,
+    DEFAULT_CATEGORY_PAGE_SIZE, DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_REQUEST_DELAY, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT,
+    MAX_CATEGORY_PAGE_SIZE, RECENT_CHANGES_MAX_AGE, RECENT_MODS_MAX_PAGES,
+};
+pub use search::{
+    calculate_regex_score, calculate_search_score, calculate_search_score_in, matching_words, search, SearchField,
+    SearchOpts,
+};
+pub use sitemap::sitemap_xml;
+pub use snapshot::{Snapshot, SNAPSHOT_VERSION};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+pub use text::{clean_text, extract_description, extract_features, normalize_name, slugify, snippet, truncate, wikilinks};
+pub use tfidf::TfIdfIndex;
+
+/// Base URL of the Balatro mods wiki.
+pub const WIKI_BASE_URL: &str = "https://balatromods.miraheze.org";
+/// Location of the cached mod database (tilde-expanded at runtime).
+pub const CACHE_FILE: &str = "~/.cache/balatro-wiki/mods.json";
@@ -1,15 +1,101 @@
 use anyhow::{anyhow, Result};
-use chrono::Utc;
-use clap::{Parser, Subcommand};
-use reqwest::Client;
-use scraper::{Html, Selector};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use balatro_wiki::{
+    cache_path, dependency_tree, dependents, matching_words, search, similar_mods, sitemap_xml, slugify, snippet,
+    star_rank, truncate, wikilinks, Bookmarks, Categories, ChangeSet, DependencyNode, HttpCache, InstalledMod,
+    InstalledMods, JsonFilter, LinkStatus, ModDatabase, ModInfo, RecentMods, ScraperConfig, SearchField, SearchOpts,
+    Snapshot, TfIdfIndex, UpdateFailures, UpdateProgress, UpdateTimings, WikiScraper, DEFAULT_CATEGORY_PAGE_SIZE,
+    DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_REQUEST_DELAY, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT, WIKI_BASE_URL,
+};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Parser, Subcommand, ValueEnum};
+use minijinja::{context, Environment};
+use owo_colors::{OwoColorize, Stream, Style};
+use regex::{Regex, RegexBuilder};
+use std::io::IsTerminal;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "sqlite")]
+use balatro_wiki::SqliteStore;
 
 #[derive(Parser)]
 #[command(name = "balatro-wiki")]
 #[command(about = "A CLI tool for browsing and searching Balatro mods from the wiki")]
 struct Cli {
+    /// Use only the cached database and never contact the wiki
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Named mod collection to use instead of the main cache (see `update --profile`)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// HTTP request timeout in seconds
+    #[arg(long, global = true, value_name = "SECS", default_value_t = DEFAULT_TIMEOUT.as_secs())]
+    timeout: u64,
+
+    /// Log scraping progress, including when a read command refreshes a stale cache
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Suppress progress logging, including during `update`
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Minimum milliseconds between requests to the wiki (0 to disable)
+    #[arg(long, global = true, value_name = "MS", default_value_t = DEFAULT_REQUEST_DELAY.as_millis() as u64)]
+    request_delay: u64,
+
+    /// Category members to fetch per wiki API request (capped at 500); larger pages mean fewer requests
+    #[arg(long, global = true, value_name = "N", default_value_t = DEFAULT_CATEGORY_PAGE_SIZE,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    category_page_size: u32,
+
+    /// Refresh cached categories older than this before reading them, e.g. 7d, or 0 to always refresh
+    #[arg(long, global = true, value_name = "DURATION", env = "BALATRO_WIKI_MAX_AGE", default_value = "24h",
+          value_parser = parse_duration)]
+    max_age: chrono::Duration,
+
+    /// Root URL of the wiki to scrape, e.g. a mirror
+    #[arg(long, global = true, env = "BALATRO_WIKI_URL", default_value = WIKI_BASE_URL, value_parser = parse_wiki_url)]
+    wiki_url: String,
+
+    /// User-agent sent to the wiki
+    #[arg(long, global = true, env = "BALATRO_WIKI_UA", default_value = DEFAULT_USER_AGENT,
+          value_parser = parse_user_agent)]
+    user_agent: String,
+
+    /// Print Browse, Search, and Info results as JSON (same as `--output json`)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Keep only these fields of each mod in JSON output (comma-separated), e.g. name,github_url
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(ModInfo::FIELD_NAMES.iter().copied())
+    )]
+    json_fields: Vec<String>,
+
+    /// Never color output (also set by NO_COLOR; color is off when stdout isn't a terminal)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Read the mod database from this JSON file ('-' for stdin) instead of the cache, without
+    /// contacting the wiki (browse, search, and info only)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
+    /// Where the cache is stored (sqlite needs a build with the `sqlite` feature, and supports
+    /// update, browse, search, info, count, and categories)
+    #[arg(long, global = true, value_enum, env = "BALATRO_WIKI_BACKEND", default_value_t = Backend::Json)]
+    backend: Backend,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -20,367 +106,633 @@ enum Commands {
     Browse {
         /// Category to browse (content, joker, qol, crossover, technical, api)
         category: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+        /// Columns to include in TSV output (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', default_values_t = Field::default_tsv_columns())]
+        tsv_columns: Vec<Field>,
+        /// Fields to show for each mod (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', conflicts_with = "exclude_fields")]
+        fields: Vec<Field>,
+        /// Fields to hide for each mod, showing all others (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        exclude_fields: Vec<Field>,
+        /// Show only mods that are not in your favorites (see `fav`)
+        #[arg(long, conflicts_with = "bookmarked_only")]
+        not_bookmarked: bool,
+        /// Show only mods that are in your favorites (see `fav`)
+        #[arg(long)]
+        bookmarked_only: bool,
+        /// Show only mods whose GitHub repository has published a Release
+        #[arg(long)]
+        only_mods_with_releases: bool,
+        /// Hide mods without a GitHub repository
+        #[arg(long)]
+        only_with_github: bool,
+        /// Show only mods for which this jq expression is true, e.g. '.author != null'
+        #[arg(long, value_name = "JQ_EXPR")]
+        filter_json: Option<String>,
+        /// Order the listed mods (default: category order, or by name when listing all)
+        #[arg(long, value_enum)]
+        sort: Option<SortKey>,
+        /// Show only mods whose name starts with this letter or a later one
+        #[arg(long, value_name = "LETTER")]
+        after_alphabetical: Option<char>,
+        /// List in the opposite order, applied after --sort
+        #[arg(long)]
+        reverse: bool,
+        /// Show one line per mod: name │ category │ author │ version
+        #[arg(long)]
+        compact: bool,
+        /// With --compact, omit the heading line
+        #[arg(long, requires = "compact")]
+        no_header: bool,
+        /// Cut descriptions to this many characters (0 shows them in full)
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_DESC_LEN)]
+        desc_len: usize,
+        /// Print only the matching mod names, one per line, for use in shell loops
+        #[arg(long, conflicts_with_all = ["output", "compact"])]
+        name_only: bool,
     },
     /// Search for mods by name or description
     Search {
         /// Search query
         query: String,
+        /// Treat the query as a regular expression matched against each searched field
+        #[arg(long)]
+        regex: bool,
+        /// Make the --regex pattern case-insensitive (same as a leading `(?i)`)
+        #[arg(long, requires = "regex")]
+        ignore_case: bool,
+        /// Rank by TF-IDF similarity to the query (see `index`) instead of substring matches
+        #[arg(long, conflicts_with = "regex")]
+        semantic: bool,
+        /// Only search mods in this category (full name or alias, e.g. joker)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only search mods by this author
+        #[arg(long)]
+        author: Option<String>,
+        /// Only search mods with this infobox tag, e.g. Joker or Deck
+        #[arg(long)]
+        tag: Option<String>,
+        /// Hide mods without a GitHub repository
+        #[arg(long)]
+        only_with_github: bool,
+        /// Only match the query against these fields (comma-separated), or `all` [default: all]
+        #[arg(
+            long = "in",
+            alias = "fields-to-search",
+            value_name = "FIELDS",
+            value_delimiter = ',',
+            value_parser = search_field_parser()
+        )]
+        fields_to_search: Vec<Option<SearchField>>,
+        /// Require every word of the query to match (default: any word)
+        #[arg(long, conflicts_with_all = ["regex", "semantic"])]
+        all: bool,
+        /// Order the results by this instead of by score, then list the top ones
+        #[arg(long, value_enum)]
+        sort: Option<SortKey>,
+        /// List the top results in the opposite order, e.g. lowest score first
+        #[arg(long)]
+        reverse: bool,
+        /// Hide matches scoring below this, e.g. 25 to require a name or description match
+        ///
+        /// Each query word scores 100 for an exact name match or 50 for a name substring,
+        /// plus 25 for a description, 20 for an author, and 15 for a category match; a mod's
+        /// score is the sum over words. A query of several words adds 100 more when it is the
+        /// whole name, or 50 when the name contains it as a phrase. With --regex, a mod scores
+        /// 100 for a whole-name match or 50 within the name, plus 25 for a description, 20 for
+        /// an author, and 15 for a category match.
+        #[arg(long, value_name = "N", default_value_t = 0, conflicts_with = "semantic")]
+        min_score: i32,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+        /// Columns to include in TSV output (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', default_values_t = Field::default_tsv_columns())]
+        tsv_columns: Vec<Field>,
+        /// Only highlight matches in these fields (comma-separated; default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        highlight_fields: Vec<Field>,
+        /// Show the part of each description around its first match, with matches in bold
+        /// (or wrapped in ** when color is off)
+        #[arg(long, conflicts_with = "semantic")]
+        highlight: bool,
+        /// Cut descriptions to this many characters (0 shows them in full)
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_DESC_LEN)]
+        desc_len: usize,
+        /// Print only the matching mod names, one per line, best match first
+        #[arg(long, conflicts_with = "output")]
+        name_only: bool,
     },
     /// Get detailed information about a specific mod
     Info {
         /// Mod name
         name: String,
+        /// Re-scrape the mod page and show fields that differ from the cache
+        #[arg(long)]
+        diff_against_live: bool,
+        /// Store the freshly scraped info in the cache when it differs
+        #[arg(long, requires = "diff_against_live")]
+        update: bool,
+        /// Open the mod's wiki page or GitHub repository in the default browser
+        #[arg(long, value_enum, value_name = "TARGET")]
+        open: Option<OpenTarget>,
+        /// Deprecated spelling of --open wiki
+        #[arg(short = 'o', long, hide = true, conflicts_with = "open")]
+        open_in_browser: bool,
+        /// Deprecated: with --open-in-browser, the same as --open github
+        #[arg(long, hide = true, requires = "open_in_browser")]
+        github: bool,
+        /// Print only this template, e.g. '{name}\t{github_url}' (placeholders: name,
+        /// description, author, version, github_url, wiki_url, category)
+        #[arg(long, conflicts_with = "diff_against_live")]
+        format: Option<String>,
+    },
+    /// Report on a mod: details, popularity, dependency tree, dependents, and similar mods
+    Analyze {
+        /// Mod name
+        name: String,
+    },
+    /// Build the TF-IDF index used by `search --semantic`
+    Index,
+    /// Compare two or more mods side by side
+    Compare {
+        /// Mod names (partial names are accepted when unambiguous)
+        #[arg(required = true, num_args = 2..)]
+        names: Vec<String>,
+    },
+    /// Export the mod database as a JSON array of mods
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Write one Markdown page per mod into this directory, plus an index.md, instead of JSON
+        #[arg(long, value_name = "DIR", conflicts_with = "output")]
+        output_dir: Option<PathBuf>,
+        /// Only export mods whose data changed after this time (RFC 3339 or YYYY-MM-DD)
+        #[arg(long, value_name = "SINCE_TIMESTAMP", value_parser = parse_timestamp)]
+        incremental: Option<DateTime<Utc>>,
+    },
+    /// Write the mod database, bookmarks, favorites, and installed manifest to one file for sharing or safekeeping
+    Snapshot {
+        /// Snapshot file to write
+        output: PathBuf,
+    },
+    /// Replace the local cache with a snapshot, backing up the current one first
+    ///
+    /// The restored categories count as freshly scraped, so they aren't refreshed from the wiki
+    /// until --max-age passes; run `update` to bring them up to date sooner.
+    Restore {
+        /// Snapshot file written by `snapshot`
+        input: PathBuf,
     },
     /// List all available categories
     Categories,
-    /// Update the local mod database
-    Update,
+    /// Print the number of mods, in total or in one category, as a bare integer
+    Count {
+        /// Category to count (full name or alias, e.g. joker)
+        category: Option<String>,
+    },
+    /// Check the cache, wiki connectivity, and optional tools
+    Doctor,
+    /// Check that every mod's GitHub link still resolves, reporting broken and redirected ones
+    CheckLinks {
+        /// How many links to check at once (each request gives up after --timeout)
+        #[arg(long, value_name = "N", default_value_t = 8, value_parser = clap::value_parser!(u64).range(1..))]
+        concurrency: u64,
+    },
+    /// Show what the last update added, removed, or re-versioned
+    Changed {
+        /// Only show the changes if that update ran within this long, e.g. 7d or 48h
+        #[arg(long, value_name = "DURATION", value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+    },
+    /// Show the most recently created or edited mod pages on the wiki
+    Recent {
+        /// Number of mods to show [default: 10, or all with --since]
+        limit: Option<usize>,
+        /// Only show mods edited within this long, e.g. 7d, 48h, 30m, or 2w
+        #[arg(long, value_name = "DURATION", value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+    },
+    /// Add a mod to your favorites (favorites.json)
+    Fav {
+        /// Mod name
+        name: String,
+    },
+    /// Remove a mod from your favorites
+    Unfav {
+        /// Mod name
+        name: String,
+    },
+    /// Show your favorite mods
+    Favs,
+    /// Install a mod from its GitHub repository with balatro-install-mod and track it
+    Install {
+        /// Mod name (partial names are accepted when unambiguous)
+        name: String,
+        /// Only record the mod as installed, without running the installer
+        #[arg(long)]
+        record_only: bool,
+    },
+    /// List tracked installed mods, flagging ones that left the wiki or have a newer version
+    Installed,
+    /// Stop tracking an installed mod
+    Uninstall {
+        /// Mod name
+        name: String,
+        /// Also delete the mod's folder with balatro-remove-mod
+        #[arg(long)]
+        remove_files: bool,
+    },
+    /// Poll the wiki and report newly added mods until Ctrl-C
+    Watch {
+        /// Only watch this wiki category, e.g. "Joker Mods" (default: refresh the whole database)
+        #[arg(long)]
+        category: Option<String>,
+        /// Minutes between polls
+        #[arg(long, value_name = "MINUTES", default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Seconds between polls, for intervals finer than --interval allows
+        #[arg(long, value_name = "SECS", conflicts_with = "interval", value_parser = clap::value_parser!(u64).range(1..))]
+        interval_secs: Option<u64>,
+    },
+    /// Update the local mod database (or the named --profile database)
+    Update {
+        /// Keep this file updated with JSON progress while scraping; removed when done
+        #[arg(long)]
+        progress_file: Option<PathBuf>,
+        /// Cap the total request rate, e.g. 2.5 requests per second (overrides --request-delay)
+        #[arg(long, value_name = "REQUESTS_PER_SECOND", value_parser = parse_rate)]
+        rate_limit: Option<f64>,
+        /// Refresh only this category's mods (full name or alias), keeping the rest of the cache
+        #[arg(long, conflicts_with = "progress_file")]
+        category: Option<String>,
+        /// After updating, write a sitemap.xml of every mod's wiki page to this path
+        #[arg(long, value_name = "PATH")]
+        generate_sitemap: Option<PathBuf>,
+        /// After updating, report [[wikilinks]] in descriptions that point to missing pages
+        #[arg(long)]
+        verify_wikipedia_links: bool,
+        /// Only re-scrape pages changed on the wiki after this time (RFC 3339 or YYYY-MM-DD)
+        #[arg(long, value_parser = parse_timestamp, conflicts_with_all = ["category", "progress_file"])]
+        since: Option<DateTime<Utc>>,
+        /// Like --since, starting from when the cached database was last updated
+        #[arg(long, conflicts_with_all = ["since", "category", "progress_file"])]
+        since_last_run: bool,
+        /// Print each mod to stdout as a JSON line as soon as it is scraped (full updates only)
+        #[arg(
+            long,
+            conflicts_with_all = ["category", "since", "since_last_run", "generate_sitemap", "verify_wikipedia_links"]
+        )]
+        stream: bool,
+        /// Also fetch each mod's GitHub star count (one GitHub API request per mod)
+        #[arg(long)]
+        github_stars: bool,
+
+        /// Crawl and report mods per category and scrape failures, without saving anything
+        #[arg(long, conflicts_with = "generate_sitemap")]
+        dry_run: bool,
+
+        /// After a full update, report how long category listings and mod pages took, and the slowest mods
+        #[arg(long, conflicts_with_all = ["category", "since", "since_last_run"])]
+        timings: bool,
+
+        /// Re-scrape every mod page, even those not edited since the cached copy was scraped.
+        /// Reused pages also keep their cached GitHub Releases check, which this refreshes
+        #[arg(long, conflicts_with_all = ["category", "since", "since_last_run"])]
+        refetch_all: bool,
+
+        /// Fail at the first category or mod page that can't be scraped, instead of skipping it
+        #[arg(long)]
+        strict: bool,
+
+        /// Don't log each category or mod page that can't be scraped, only how many there were
+        #[arg(long, conflicts_with = "strict")]
+        quiet_errors: bool,
+    },
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct ModInfo {
-    name: String,
-    description: String,
-    author: Option<String>,
-    version: Option<String>,
-    github_url: Option<String>,
-    wiki_url: String,
-    category: String,
-    dependencies: Vec<String>,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Decorated human-readable output
+    Text,
+    /// Tab-separated values, one mod per line
+    Tsv,
+    /// A self-contained HTML page of mod cards with a search filter
+    Html,
+    /// Aligned name, category, author, and GitHub columns, fitted to the terminal width
+    Table,
+    /// A JSON array of mods, as stored in the cache (see `--json-fields`)
+    Json,
+    /// Just the mod names, one per line (see `--name-only`)
+    #[value(skip)]
+    Names,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ModDatabase {
-    mods: HashMap<String, ModInfo>,
-    categories: HashMap<String, Vec<String>>,
-    last_updated: String,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Backend {
+    /// The whole database in mods.json
+    Json,
+    /// Indexed mods, categories, and dependencies tables in mods.sqlite beside mods.json
+    Sqlite,
 }
 
-const WIKI_BASE_URL: &str = "https://balatromods.miraheze.org";
-const CACHE_FILE: &str = "~/.cache/balatro-wiki/mods.json";
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OpenTarget {
+    /// The mod's wiki page
+    Wiki,
+    /// The mod's GitHub repository
+    Github,
+}
 
-impl ModDatabase {
-    fn new() -> Self {
-        Self {
-            mods: HashMap::new(),
-            categories: HashMap::new(),
-            last_updated: Utc::now().to_rfc3339(),
-        }
-    }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum SortKey {
+    /// Alphabetically by name
+    Name,
+    /// Most wiki kudos first; mods without a count last
+    Kudos,
+    /// Most GitHub stars first (see `update --github-stars`); mods without a count last
+    Stars,
+    /// Most recently edited wiki page first; mods without an edit time last
+    Age,
+}
 
-    fn load_or_create() -> Result<Self> {
-        let cache_path = shellexpand::tilde(CACHE_FILE);
-        let cache_path = std::path::Path::new(cache_path.as_ref());
-        
-        if cache_path.exists() {
-            let content = std::fs::read_to_string(cache_path)?;
-            Ok(serde_json::from_str(&content)?)
-        } else {
-            Ok(Self::new())
+impl SortKey {
+    /// Sorts items that each carry a mod. Ties keep their order.
+    fn sort_by<T>(&self, items: &mut [T], mod_of: impl Fn(&T) -> &ModInfo) {
+        match self {
+            SortKey::Name => items.sort_by(|a, b| mod_of(a).name.cmp(&mod_of(b).name)),
+            SortKey::Kudos => items.sort_by_key(|item| std::cmp::Reverse(mod_of(item).kudos_count)),
+            SortKey::Stars => items.sort_by_key(|item| std::cmp::Reverse(mod_of(item).stars)),
+            SortKey::Age => items.sort_by_key(|item| {
+                let edited = mod_of(item).last_edited.as_deref().and_then(|ts| DateTime::parse_from_rfc3339(ts).ok());
+                std::cmp::Reverse(edited)
+            }),
         }
     }
+}
 
-    fn should_update(&self) -> bool {
-        if self.mods.is_empty() {
-            return true;
-        }
-        
-        // Parse last_updated timestamp
-        if let Ok(last_updated) = chrono::DateTime::parse_from_rfc3339(&self.last_updated) {
-            let now = Utc::now();
-            let age = now.signed_duration_since(last_updated.with_timezone(&Utc));
-            
-            // Update if older than 24 hours
-            age.num_hours() >= 24
-        } else {
-            true // Invalid timestamp, force update
-        }
+/// Keeps the 20 results a search lists: the best-scoring ones, or the first
+/// ones by `--sort` when given, in the opposite order with `--reverse`.
+fn top_results<T>(matches: &mut Vec<T>, sort: Option<SortKey>, reverse: bool, mod_of: impl Fn(&T) -> &ModInfo) {
+    if let Some(sort) = sort {
+        sort.sort_by(matches, mod_of);
+    }
+    matches.truncate(20);
+    if reverse {
+        matches.reverse();
     }
+}
 
-    async fn ensure_fresh(scraper: &WikiScraper) -> Result<Self> {
-        Self::ensure_fresh_with_verbosity(scraper, true).await
+/// Applies `--sort`, if given, and then `--reverse` to a listing.
+fn arrange<T>(items: &mut [T], sort: Option<SortKey>, reverse: bool, mod_of: impl Fn(&T) -> &ModInfo) {
+    if let Some(sort) = sort {
+        sort.sort_by(items, mod_of);
     }
-    
-    async fn ensure_fresh_silent(scraper: &WikiScraper) -> Result<Self> {
-        Self::ensure_fresh_with_verbosity(scraper, false).await
+    if reverse {
+        items.reverse();
     }
-    
-    async fn ensure_fresh_with_verbosity(scraper: &WikiScraper, verbose: bool) -> Result<Self> {
-        let mut db = Self::load_or_create()?;
-        
-        if db.should_update() {
-            if verbose {
-                println!("🔄 Updating mod database...");
-            }
-            db = scraper.update_database_with_verbosity(verbose).await?;
-            db.save()?;
-            if verbose {
-                println!("✅ Database updated with {} mods", db.mods.len());
-            }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Field {
+    Name,
+    Description,
+    Author,
+    Version,
+    #[value(name = "github_url")]
+    GithubUrl,
+    #[value(name = "wiki_url")]
+    WikiUrl,
+    Category,
+    Dependencies,
+}
+
+impl Field {
+    fn default_tsv_columns() -> Vec<Field> {
+        vec![Field::Name, Field::Category, Field::Author, Field::Version, Field::GithubUrl]
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Description => "description",
+            Field::Author => "author",
+            Field::Version => "version",
+            Field::GithubUrl => "github_url",
+            Field::WikiUrl => "wiki_url",
+            Field::Category => "category",
+            Field::Dependencies => "dependencies",
         }
-        
-        Ok(db)
     }
 
-    fn save(&self) -> Result<()> {
-        let cache_path = shellexpand::tilde(CACHE_FILE);
-        let cache_path = std::path::Path::new(cache_path.as_ref());
-        
-        if let Some(parent) = cache_path.parent() {
-            std::fs::create_dir_all(parent)?;
+    fn value(&self, mod_info: &ModInfo) -> String {
+        match self {
+            Field::Name => mod_info.name.clone(),
+            Field::Description => mod_info.description.clone(),
+            Field::Author => mod_info.author.clone().unwrap_or_default(),
+            Field::Version => mod_info.version.clone().unwrap_or_default(),
+            Field::GithubUrl => mod_info.github_url.clone().unwrap_or_default(),
+            Field::WikiUrl => mod_info.wiki_url.clone(),
+            Field::Category => mod_info.categories.join(","),
+            Field::Dependencies => mod_info.dependencies.join(","),
         }
-        
-        let content = serde_json::to_string_pretty(self)?;
-        std::fs::write(cache_path, content)?;
-        Ok(())
     }
 }
 
-struct WikiScraper {
-    client: Client,
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.header())
+    }
 }
 
-impl WikiScraper {
-    fn new() -> Self {
-        let client = Client::builder()
-            .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-            
-        Self { client }
+/// The set of fields shown for each mod in card output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldSet(Vec<Field>);
+
+impl FieldSet {
+    fn new(fields: &[Field]) -> Self {
+        Self(Field::value_variants().iter().copied().filter(|f| fields.contains(f)).collect())
     }
 
-    async fn scrape_category_page(&self, category: &str) -> Result<Vec<String>> {
-        self.scrape_category_page_with_verbosity(category, true).await
+    fn complement(&self) -> Self {
+        Self(Field::value_variants().iter().copied().filter(|f| !self.contains(*f)).collect())
     }
-    
-    async fn scrape_category_page_with_verbosity(&self, category: &str, verbose: bool) -> Result<Vec<String>> {
-        // Use MediaWiki API instead of HTML scraping
-        let api_url = format!("{}/w/api.php?action=query&list=categorymembers&cmtitle=Category:{}&format=json&cmlimit=50", 
-                             WIKI_BASE_URL, category);
-        if verbose {
-            println!("  API request: {}", api_url);
-        }
-        
-        let response = self.client
-            .get(&api_url)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
-            
-        let json_text = response.text().await?;
-        if verbose {
-            println!("  Got {} bytes of JSON", json_text.len());
-        }
-        
-        // Parse JSON response
-        let json: serde_json::Value = serde_json::from_str(&json_text)?;
-        
-        let mut mod_names = Vec::new();
-        
-        if let Some(query) = json.get("query") {
-            if let Some(categorymembers) = query.get("categorymembers") {
-                if let Some(members) = categorymembers.as_array() {
-                    if verbose {
-                        println!("  Found {} category members", members.len());
-                    }
-                    
-                    for member in members {
-                        if let Some(title) = member.get("title").and_then(|t| t.as_str()) {
-                            // Skip category pages and other namespace pages
-                            if !title.contains("Category:") && !title.contains("File:") && !title.contains("Template:") {
-                                mod_names.push(title.to_string());
-                                if verbose {
-                                    println!("    ✓ {}", title);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        if verbose {
-            println!("  Extracted {} mod names", mod_names.len());
+
+    /// Resolves `--fields`/`--exclude-fields`, falling back to `default` when neither is given.
+    fn from_args(fields: &[Field], exclude_fields: &[Field], default: &[Field]) -> Self {
+        if !fields.is_empty() {
+            Self::new(fields)
+        } else if !exclude_fields.is_empty() {
+            Self::new(exclude_fields).complement()
+        } else {
+            Self::new(default)
         }
-        Ok(mod_names)
     }
 
-    async fn scrape_mod_page(&self, mod_name: &str) -> Result<ModInfo> {
-        let url = format!("{}/wiki/{}", WIKI_BASE_URL, mod_name);
-        let response = self.client.get(&url).send().await?;
-        let html = response.text().await?;
-        let document = Html::parse_document(&html);
-        
-        // Extract basic info
-        let title_selector = Selector::parse("h1.firstHeading").unwrap();
-        let name = document
-            .select(&title_selector)
-            .next()
-            .map(|el| el.text().collect::<String>())
-            .unwrap_or_else(|| mod_name.to_string());
-
-        // Extract description from multiple sources
-        let description = extract_description(&document);
-
-        // Look for GitHub links
-        let link_selector = Selector::parse("a[href*='github.com']").unwrap();
-        let github_url = document
-            .select(&link_selector)
-            .next()
-            .and_then(|el| el.value().attr("href"))
-            .map(|s| s.to_string());
-
-        // Extract from infobox if present
-        let infobox_selector = Selector::parse(".infobox tr").unwrap();
-        let mut author = None;
-        let mut version = None;
-        
-        for row in document.select(&infobox_selector) {
-            let text = row.text().collect::<String>();
-            if text.to_lowercase().contains("author") {
-                // Extract author from next sibling or same row
-                author = Some("Unknown".to_string()); // Simplified for now
-            }
-            if text.to_lowercase().contains("version") {
-                version = Some("Unknown".to_string()); // Simplified for now
-            }
-        }
-
-        Ok(ModInfo {
-            name,
-            description: description.trim().to_string(),
-            author,
-            version,
-            github_url,
-            wiki_url: url,
-            category: "Unknown".to_string(), // Will be set by caller
-            dependencies: Vec::new(),
-        })
+    fn contains(&self, field: Field) -> bool {
+        self.0.contains(&field)
     }
+}
+
+/// Parses a `search --in` value: a search field, or `None` for `all`.
+fn search_field_parser() -> impl TypedValueParser<Value = Option<SearchField>> {
+    let names = SearchField::ALL.map(|f| f.as_str()).into_iter().chain(["all"]);
+    PossibleValuesParser::new(names)
+        .map(|s| (s != "all").then(|| s.parse::<SearchField>().expect("restricted to known search fields")))
+}
 
-    async fn update_database(&self) -> Result<ModDatabase> {
-        self.update_database_with_verbosity(true).await
+/// The fields `search --in` lets contribute to the score; empty (every
+/// field) when none were given or `all` was.
+fn search_fields(values: Vec<Option<SearchField>>) -> Vec<SearchField> {
+    if values.contains(&None) {
+        return Vec::new();
     }
-    
-    async fn update_database_with_verbosity(&self, verbose: bool) -> Result<ModDatabase> {
-        let mut db = ModDatabase::new();
-        
-        let categories = vec![
-            ("Content Mods", "Content%20Mods"),
-            ("Joker Mods", "Joker%20Mods"),
-            ("Quality of Life Mods", "Quality%20of%20Life%20Mods"),
-            ("Crossover Mods", "Crossover%20Mods"),
-            ("Technical Mods", "Technical%20Mods"),
-            ("API Mods", "API%20Mods"),
-        ];
+    values.into_iter().flatten().collect()
+}
 
-        // Collect all mod names from all categories first
-        let mut all_mod_names = std::collections::HashSet::new();
-        let mut mod_categories = std::collections::HashMap::new();
-        
-        for (category_name, wiki_category) in &categories {
-            if verbose {
-                println!("Collecting mods from category: {}", category_name);
-            }
-            
-            match self.scrape_category_page_with_verbosity(wiki_category, verbose).await {
-                Ok(mod_names) => {
-                    for mod_name in mod_names {
-                        all_mod_names.insert(mod_name.clone());
-                        mod_categories.insert(mod_name, category_name.to_string());
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Failed to scrape category {}: {}", category_name, e);
-                }
-            }
-        }
-        
-        if verbose {
-            println!("Processing {} unique mods concurrently...", all_mod_names.len());
+const BROWSE_CARD_FIELDS: &[Field] = &[Field::Name, Field::Description, Field::Author, Field::GithubUrl];
+const SEARCH_CARD_FIELDS: &[Field] = &[Field::Name, Field::Category, Field::Description, Field::GithubUrl];
+
+/// Display and filtering options for Browse.
+struct BrowseOpts {
+    output: OutputFormat,
+    json_fields: Vec<String>,
+    tsv_columns: Vec<Field>,
+    fields: FieldSet,
+    bookmark_filter: Option<BookmarkFilter>,
+    only_with_releases: bool,
+    only_with_github: bool,
+    json_filter: Option<JsonFilter>,
+    sort: Option<SortKey>,
+    reverse: bool,
+    after_letter: Option<char>,
+    compact: bool,
+    no_header: bool,
+    desc_len: usize,
+}
+
+/// Display options for Search results.
+struct SearchView {
+    output: OutputFormat,
+    json_fields: Vec<String>,
+    tsv_columns: Vec<Field>,
+    highlight_fields: Vec<Field>,
+    snippets: bool,
+    only_with_github: bool,
+    sort: Option<SortKey>,
+    reverse: bool,
+    verbose: bool,
+    desc_len: usize,
+}
+
+enum BookmarkFilter {
+    Only(Bookmarks),
+    Exclude(Bookmarks),
+}
+
+impl BrowseOpts {
+    fn is_filtering(&self) -> bool {
+        self.bookmark_filter.is_some()
+            || self.only_with_releases
+            || self.only_with_github
+            || self.json_filter.is_some()
+            || self.after_letter.is_some()
+    }
+
+    fn accepts(&self, mod_info: &ModInfo) -> bool {
+        if self.only_with_releases && mod_info.has_github_releases != Some(true) {
+            return false;
         }
-        
-        // Process all mods concurrently
-        let mut handles = Vec::new();
-        for mod_name in all_mod_names.iter() {
-            let client = self.client.clone();
-            let name = mod_name.clone();
-            let handle = tokio::spawn(async move {
-                let scraper = WikiScraper { client };
-                (name.clone(), scraper.scrape_mod_page(&name).await)
-            });
-            handles.push(handle);
+        if let Some(letter) = self.after_letter {
+            let first = mod_info.name.chars().next().map(|c| c.to_ascii_uppercase());
+            if first.is_none_or(|c| c < letter.to_ascii_uppercase()) {
+                return false;
+            }
         }
-        
-        // Collect results and organize by category
-        let mut category_mods: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
-        for (category_name, _) in &categories {
-            category_mods.insert(category_name.to_string(), Vec::new());
+        match &self.bookmark_filter {
+            Some(BookmarkFilter::Only(bookmarks)) => bookmarks.contains(&mod_info.name),
+            Some(BookmarkFilter::Exclude(bookmarks)) => !bookmarks.contains(&mod_info.name),
+            None => true,
         }
-        
-        for handle in handles {
-            match handle.await {
-                Ok((mod_name, result)) => {
-                    match result {
-                        Ok(mut mod_info) => {
-                            if let Some(category) = mod_categories.get(&mod_name) {
-                                mod_info.category = category.to_string();
-                                if let Some(cat_mods) = category_mods.get_mut(category) {
-                                    cat_mods.push(mod_info.name.clone());
-                                }
-                                db.mods.insert(mod_info.name.clone(), mod_info);
-                                if verbose {
-                                    println!("  ✓ {}", mod_name);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            if verbose {
-                                eprintln!("  ✗ Failed to scrape {}: {}", mod_name, e);
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    if verbose {
-                        eprintln!("  ✗ Task failed: {}", e);
-                    }
+    }
+}
+
+async fn browse_mods(
+    db: &ModDatabase,
+    categories: &Categories,
+    category: Option<String>,
+    opts: &BrowseOpts,
+) -> Result<()> {
+    let category = match category {
+        Some(cat) => match db.resolve_category(categories, &cat) {
+            Some(key) => Some(key.to_string()),
+            None => {
+                let hint = db.suggest_category(categories, &cat)
+                    .map(|key| format!(" (did you mean '{}'?)", key))
+                    .unwrap_or_default();
+                if opts.output != OutputFormat::Text {
+                    return Err(anyhow!("Category '{}' not found{}", cat, hint));
                 }
+                println!("Category '{}' not found{}. Available categories:", cat, hint);
+                list_categories(db, categories);
+                return Ok(());
             }
+        },
+        None => None,
+    };
+    let mut mods: Vec<&ModInfo> = match &category {
+        Some(cat) => db.categories[cat].iter().filter_map(|name| db.mods.get(name)).collect(),
+        None => {
+            let mut all: Vec<&ModInfo> = db.mods.values().collect();
+            all.sort_by(|a, b| a.name.cmp(&b.name));
+            all
         }
-        
-        db.categories = category_mods;
-        Ok(db)
-    }
-}
-
-async fn browse_mods(db: &ModDatabase, category: Option<String>) -> Result<()> {
-    match category {
-        Some(cat) => {
-            if let Some(mod_names) = db.categories.get(&cat) {
-                println!("🎮 {} ({} mods):", cat, mod_names.len());
-                println!("{}", "─".repeat(50));
-                
-                for mod_name in mod_names {
-                    if let Some(mod_info) = db.mods.get(mod_name) {
-                        println!("🃏 {}", mod_info.name);
-                        println!("   {}", truncate(&mod_info.description, 300));
-                        if let Some(author) = &mod_info.author {
-                            println!("   👤 by {}", author);
-                        }
-                        if let Some(github) = &mod_info.github_url {
-                            println!("   🔗 {}", github);
-                        }
-                        println!();
-                    }
-                }
-            } else {
-                println!("Category '{}' not found. Available categories:", cat);
-                list_categories(db);
-            }
+    };
+    mods.retain(|mod_info| opts.accepts(mod_info));
+    if let Some(filter) = &opts.json_filter {
+        mods = mods.into_iter()
+            .filter_map(|mod_info| filter.matches(mod_info).map(|keep| keep.then_some(mod_info)).transpose())
+            .collect::<Result<_>>()?;
+    }
+    let hidden = hide_without_github(&mut mods, opts.only_with_github, |mod_info| mod_info);
+    arrange(&mut mods, opts.sort, opts.reverse, |mod_info| mod_info);
+    
+    if opts.output == OutputFormat::Tsv {
+        print_tsv(&mods, &opts.tsv_columns);
+        return Ok(());
+    }
+    if opts.output == OutputFormat::Html {
+        let title = match &category {
+            Some(cat) => format!("Balatro Mods: {}", cat),
+            None => "Balatro Mods".to_string(),
+        };
+        println!("{}", render_html(&title, &mods)?);
+        return Ok(());
+    }
+    if opts.output == OutputFormat::Table {
+        print_table(&mods);
+        return Ok(());
+    }
+    if opts.output == OutputFormat::Json {
+        return print_json(&mods, &opts.json_fields);
+    }
+    if opts.output == OutputFormat::Names {
+        print_names(&mods);
+        return Ok(());
+    }
+
+    let header = match &category {
+        Some(cat) => format!("🎮 {} ({} mods{}):", cat, mods.len(), hidden_note(hidden)),
+        None if opts.is_filtering() || opts.sort.is_some() || opts.reverse => {
+            format!("📦 Matching Balatro Mods ({} of {} total{}):", mods.len(), db.mods.len(), hidden_note(hidden))
         }
+        None if opts.compact => format!("📦 All Balatro Mods ({} total):", db.mods.len()),
         None => {
             println!("📦 All Balatro Mods ({} total):", db.mods.len());
             println!("{}", "─".repeat(50));
@@ -390,283 +742,2274 @@ async fn browse_mods(db: &ModDatabase, category: Option<String>) -> Result<()> {
                 println!("🗂️  {} ({} mods)", category, count);
             }
             println!("\nUse 'browse <category>' to see mods in a specific category");
+            return Ok(());
         }
-    }
-    Ok(())
-}
-
-fn search_mods(db: &ModDatabase, query: &str) -> Result<()> {
-    let query_lower = query.to_lowercase();
-    let mut matches = Vec::new();
-    
-    for mod_info in db.mods.values() {
-        let score = calculate_search_score(mod_info, &query_lower);
-        if score > 0 {
-            matches.push((mod_info, score));
-        }
-    }
-    
-    matches.sort_by(|a, b| b.1.cmp(&a.1));
+    };
     
-    if matches.is_empty() {
-        println!("No mods found matching '{}'", query);
-        return Ok(());
+    if !opts.no_header {
+        println!("{}", header);
+        println!("{}", "─".repeat(50));
     }
-    
-    println!("🔍 Search results for '{}' ({} matches):", query, matches.len());
-    println!("{}", "─".repeat(50));
-    
-    for (mod_info, _score) in matches.iter().take(20) {
-        println!("🃏 {}", mod_info.name);
-        println!("   📁 {}", mod_info.category);
-        println!("   {}", truncate(&mod_info.description, 300));
-        if let Some(github) = &mod_info.github_url {
-            println!("   🔗 {}", github);
+    for mod_info in mods {
+        if opts.compact {
+            print_compact_line(mod_info);
+        } else {
+            print_mod_card(mod_info, &opts.fields, None, opts.desc_len);
         }
-        println!();
     }
-    
     Ok(())
 }
 
-fn show_mod_info(db: &ModDatabase, name: &str) -> Result<()> {
-    let mod_info = db.mods.values()
-        .find(|m| m.name.to_lowercase() == name.to_lowercase())
-        .ok_or_else(|| anyhow!("Mod '{}' not found", name))?;
-    
-    println!("🃏 {}", mod_info.name);
-    println!("{}", "═".repeat(50));
-    println!("📁 Category: {}", mod_info.category);
-    println!("📝 Description: {}", mod_info.description);
-    
-    if let Some(author) = &mod_info.author {
-        println!("👤 Author: {}", author);
+/// Prints each mod's name on its own line, undecorated.
+fn print_names(mods: &[&ModInfo]) {
+    for mod_info in mods {
+        println!("{}", mod_info.name);
     }
+}
+
+/// Prints `name │ category │ author │ version`, each cut to a fixed width.
+fn print_compact_line(mod_info: &ModInfo) {
+    let cells = [
+        (mod_info.name.clone(), 28),
+        (mod_info.category_label(), 22),
+        (mod_info.author.clone().unwrap_or_else(|| "-".to_string()), 18),
+        (mod_info.version.clone().unwrap_or_else(|| "-".to_string()), 10),
+    ];
+    let line: Vec<String> = cells.iter()
+        .map(|(value, width)| format!("{:width$}", clip(value, *width), width = *width))
+        .collect();
+    println!("{}", line.join(" │ ").trim_end());
+}
+
+/// Prints `mods` as aligned `Name │ Category │ Author │ GitHub` rows. On a
+/// terminal, the widest text columns are narrowed until a row fits its width.
+fn print_table(mods: &[&ModInfo]) {
+    const HEADER: [&str; 4] = ["Name", "Category", "Author", "GitHub"];
+    const MIN_WIDTH: usize = 6;
+    let rows: Vec<[String; 4]> = mods.iter()
+        .map(|mod_info| [
+            mod_info.name.clone(),
+            mod_info.category_label(),
+            mod_info.author.clone().unwrap_or_else(|| "-".to_string()),
+            if mod_info.github_url.is_some() { "yes" } else { "no" }.to_string(),
+        ])
+        .collect();
     
-    if let Some(version) = &mod_info.version {
-        println!("📦 Version: {}", version);
+    let mut widths = HEADER.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
     }
-    
-    if let Some(github) = &mod_info.github_url {
-        println!("🔗 GitHub: {}", github);
-        println!("\n💾 To install this mod:");
-        println!("   balatro-install-mod {}", github);
+    if let Some((terminal_size::Width(columns), _)) = terminal_size::terminal_size() {
+        let available = (columns as usize).saturating_sub(" │ ".chars().count() * (HEADER.len() - 1));
+        while widths.iter().sum::<usize>() > available {
+            let widest = (0..3).max_by_key(|&i| widths[i]).unwrap_or(0);
+            if widths[widest] <= MIN_WIDTH {
+                break;
+            }
+            widths[widest] -= 1;
+        }
     }
     
-    println!("🌐 Wiki: {}", mod_info.wiki_url);
-    
-    if !mod_info.dependencies.is_empty() {
-        println!("🔗 Dependencies: {}", mod_info.dependencies.join(", "));
+    let print_row = |cells: &[&str]| {
+        let line: Vec<String> = cells.iter().zip(widths)
+            .map(|(cell, width)| format!("{:width$}", clip(cell, width)))
+            .collect();
+        println!("{}", line.join(" │ ").trim_end());
+    };
+    print_row(&HEADER);
+    let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+    println!("{}", rule.join("─┼─"));
+    for row in &rows {
+        print_row(&row.each_ref().map(String::as_str));
     }
-    
-    Ok(())
 }
 
-fn list_categories(db: &ModDatabase) {
-    println!("📂 Available categories:");
-    for (category, mods) in &db.categories {
-        println!("  {} ({} mods)", category, mods.len());
+/// Cuts `s` to at most `width` characters, ending in `…` when shortened.
+fn clip(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", kept)
     }
 }
 
-fn calculate_search_score(mod_info: &ModInfo, query: &str) -> i32 {
-    let mut score = 0;
-    
-    // Exact name match gets highest score
-    if mod_info.name.to_lowercase() == query {
-        score += 100;
-    } else if mod_info.name.to_lowercase().contains(query) {
-        score += 50;
+/// With `enabled`, drops the items whose mod has no GitHub repository and
+/// returns how many were dropped.
+fn hide_without_github<T>(items: &mut Vec<T>, enabled: bool, mod_of: impl Fn(&T) -> &ModInfo) -> Option<usize> {
+    if !enabled {
+        return None;
     }
+    let before = items.len();
+    items.retain(|item| mod_of(item).github_url.is_some());
+    Some(before - items.len())
+}
+
+/// The `, N hidden without GitHub` suffix for a results header.
+fn hidden_note(hidden: Option<usize>) -> String {
+    hidden.map(|hidden| format!(", {} hidden without GitHub", hidden)).unwrap_or_default()
+}
+
+/// Number of mods listed under `category`, which may be an alias.
+fn count_in_category(db: &ModDatabase, categories: &Categories, category: &str) -> Result<usize> {
+    let Some(key) = db.resolve_category(categories, category) else {
+        let hint = db.suggest_category(categories, category)
+            .map(|key| format!(" (did you mean '{}'?)", key))
+            .unwrap_or_default();
+        return Err(anyhow!("Category '{}' not found{}", category, hint));
+    };
+    Ok(db.categories[key].iter().filter(|name| db.mods.contains_key(*name)).count())
+}
+
+/// Prints the matches for `query` and returns how many there were.
+fn search_mods(db: &ModDatabase, query: &str, opts: SearchOpts, view: &SearchView) -> Result<usize> {
+    let highlight = Highlight::for_search(query, &opts, &view.highlight_fields, view.snippets);
+    // In verbose mode, substring results say how many query words they matched
+    let word_report = (view.verbose && !opts.regex).then(|| {
+        let fields = if opts.fields.is_empty() { SearchField::ALL.to_vec() } else { opts.fields.clone() };
+        (fields, query.split_whitespace().count())
+    });
+    let mut matches = search(db, query, opts)?;
+    let hidden = hide_without_github(&mut matches, view.only_with_github, |(mod_info, _)| mod_info);
+    let mut mods: Vec<&ModInfo> = matches.iter().map(|(mod_info, _)| mod_info).collect();
+    top_results(&mut mods, view.sort, view.reverse, |mod_info| mod_info);
     
-    // Description match
-    if mod_info.description.to_lowercase().contains(query) {
-        score += 25;
+    if view.output == OutputFormat::Tsv {
+        print_tsv(&mods, &view.tsv_columns);
+        return Ok(matches.len());
     }
-    
-    // Author match
-    if let Some(author) = &mod_info.author {
-        if author.to_lowercase().contains(query) {
-            score += 20;
-        }
-    }
-    
-    // Category match
-    if mod_info.category.to_lowercase().contains(query) {
-        score += 15;
-    }
-    
-    score
-}
-
-fn extract_description(document: &Html) -> String {
-    let mut description_parts = Vec::new();
-    
-    // Try infobox description first
-    let infobox_selector = Selector::parse(".infobox tr").unwrap();
-    for row in document.select(&infobox_selector) {
-        let cells: Vec<_> = row.select(&Selector::parse("td").unwrap()).collect();
-        if cells.len() >= 2 {
-            let header_text = cells[0].text().collect::<String>().to_lowercase();
-            if header_text.contains("description") {
-                let desc_text = cells[1].text().collect::<Vec<_>>().join(" ");
-                let cleaned = clean_text(&desc_text);
-                if cleaned.len() > 10 && !cleaned.starts_with("http") && !cleaned.contains("github.com") {
-                    description_parts.push(cleaned);
-                }
-            }
-        }
-    }
-    
-    // Extract multiple meaningful paragraphs from main content
-    let para_selector = Selector::parse("div.mw-parser-output > p").unwrap();
-    for para in document.select(&para_selector) {
-        let text = para.text().collect::<Vec<_>>().join(" ");
-        let cleaned = clean_text(&text);
-        if cleaned.len() > 20 
-            && !cleaned.chars().all(|c| c.is_whitespace()) 
-            && !cleaned.starts_with("http")
-            && !cleaned.contains("github.com")
-            && !cleaned.contains("gamebanana.com")
-            && !cleaned.contains("drive.google.com")
-            && !cleaned.to_lowercase().contains("disambiguation")
-            && !cleaned.to_lowercase().contains("redirect")
-            && !cleaned.to_lowercase().contains("this article is a stub")
-            && !cleaned.to_lowercase().contains("bibliography")
-            && !cleaned.to_lowercase().contains("references")
-            && !cleaned.to_lowercase().contains("external links")
-            && !cleaned.to_lowercase().contains("see also")
-            && !cleaned.to_lowercase().contains("categories")
-            && !cleaned.to_lowercase().contains("navigation")
-            && !cleaned.contains("2.1")
-            && !cleaned.contains("2.2")
-            && !cleaned.contains("2.3") {
-            description_parts.push(cleaned);
-            // Collect up to 3 meaningful paragraphs for fuller descriptions
-            if description_parts.len() >= 3 {
-                break;
-            }
-        }
+    if view.output == OutputFormat::Html {
+        println!("{}", render_html(&format!("Balatro Mods matching '{}'", query), &mods)?);
+        return Ok(matches.len());
     }
-    
-    // Try list items for feature descriptions
-    let list_selector = Selector::parse("div.mw-parser-output ul li").unwrap();
-    let mut features = Vec::new();
-    for item in document.select(&list_selector) {
-        let text = item.text().collect::<Vec<_>>().join(" ");
-        let cleaned = clean_text(&text);
-        if cleaned.len() > 15 
-            && !cleaned.starts_with("http") 
-            && !cleaned.contains("github.com")
-            && (cleaned.to_lowercase().contains("adds") 
-                || cleaned.to_lowercase().contains("features")
-                || cleaned.to_lowercase().contains("includes")
-                || cleaned.to_lowercase().contains("joker")) {
-            features.push(cleaned);
-            if features.len() >= 2 {
-                break;
-            }
-        }
+    if view.output == OutputFormat::Table {
+        print_table(&mods);
+        return Ok(matches.len());
     }
-    
-    // Combine all parts
-    if !features.is_empty() {
-        description_parts.extend(features);
+    if view.output == OutputFormat::Json {
+        print_json(&mods, &view.json_fields)?;
+        return Ok(matches.len());
+    }
+    if view.output == OutputFormat::Names {
+        print_names(&mods);
+        return Ok(matches.len());
     }
     
-    let combined = description_parts.join(" ");
-    if combined.len() > 10 {
-        return truncate(&combined, 500); // Increased from 200 to 500
+    if matches.is_empty() {
+        match hidden {
+            Some(hidden) if hidden > 0 => println!("No mods found matching '{}' ({} hidden without GitHub)", query, hidden),
+            _ => println!("No mods found matching '{}'", query),
+        }
+        let suggestions: Vec<&str> = db.suggest_mods(query, 3).iter().map(|m| m.name.as_str()).collect();
+        if !suggestions.is_empty() {
+            println!("💡 Did you mean: {}?", suggestions.join(", "));
+        }
+        return Ok(0);
     }
     
-    // Try any div with text content as fallback
-    let content_selector = Selector::parse("div.mw-parser-output div, div.mw-parser-output li").unwrap();
-    for element in document.select(&content_selector) {
-        let text = element.text().collect::<Vec<_>>().join(" ");
-        let cleaned = clean_text(&text);
-        if cleaned.len() > 30 
-            && !cleaned.starts_with("http")
-            && !cleaned.contains("github.com")
-            && !cleaned.contains("gamebanana.com")
-            && !cleaned.contains("drive.google.com")
-            && !cleaned.to_lowercase().contains("navigation")
-            && !cleaned.to_lowercase().contains("categories")
-            && !cleaned.to_lowercase().contains("this article is a stub") {
-            return truncate(&cleaned, 500);
+    println!("🔍 Search results for '{}' ({} matches{}):", query, matches.len(), hidden_note(hidden));
+    println!("{}", "─".repeat(50));
+    
+    let fields = FieldSet::new(SEARCH_CARD_FIELDS);
+    for mod_info in mods {
+        if let Some((score_fields, word_count)) = &word_report {
+            let matched = matching_words(mod_info, &query.to_lowercase(), score_fields);
+            println!("🔎 Matched {}/{} words", matched, word_count);
         }
+        print_mod_card(mod_info, &fields, highlight.as_ref(), view.desc_len);
     }
     
-    "No description available".to_string()
+    Ok(matches.len())
 }
 
-fn clean_text(text: &str) -> String {
-    text.trim()
-        .split_whitespace()
-        .filter(|word| !word.starts_with("http") && !word.contains("github.com") && !word.contains("gamebanana.com"))
-        .collect::<Vec<_>>()
-        .join(" ")
-        .replace("[[", "")
-        .replace("]]", "")
-        .replace("{{", "")
-        .replace("}}", "")
-        .replace("()", "")
-        .replace("  ", " ")
+/// Reads a mod database from a JSON file, or from stdin when `source` is `-`.
+/// Unlike the cache, a database that can't be parsed is an error.
+fn read_database(source: &Path) -> Result<ModDatabase> {
+    let (content, name) = if source == Path::new("-") {
+        (std::io::read_to_string(std::io::stdin())?, "stdin".to_string())
+    } else {
+        let content = std::fs::read_to_string(source)
+            .map_err(|e| anyhow!("Cannot read {}: {}", source.display(), e))?;
+        (content, source.display().to_string())
+    };
+    ModDatabase::from_json(&content).map_err(|e| anyhow!("Invalid mod database in {}: {}", name, e))
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
-    } else if max_len <= 3 {
-        "...".to_string()
-    } else {
-        let mut result = String::new();
-        let mut char_count = 0;
-        
-        for c in s.chars() {
-            if char_count + 3 >= max_len {
-                break;
+/// Loads the cache kept by `backend`, or an empty database if nothing is
+/// cached yet.
+fn load_database(backend: Backend, cache_path: &Path) -> Result<ModDatabase> {
+    match backend {
+        Backend::Json => ModDatabase::load_from(cache_path),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => SqliteStore::open(&SqliteStore::path_for(cache_path))?.load(),
+        #[cfg(not(feature = "sqlite"))]
+        Backend::Sqlite => Err(no_sqlite_support()),
+    }
+}
+
+/// Writes `db` to the cache kept by `backend`, which holds `previous`. The
+/// SQLite store only rewrites what changed since `previous`.
+#[cfg_attr(not(feature = "sqlite"), allow(unused_variables))]
+fn save_database(previous: &ModDatabase, db: &ModDatabase, backend: Backend, cache_path: &Path) -> Result<()> {
+    match backend {
+        Backend::Json => db.save_to(cache_path),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => {
+            let mut store = SqliteStore::open(&SqliteStore::path_for(cache_path))?;
+            // An empty previous database may not be what the store holds, so it is rewritten whole
+            if previous.mods.is_empty() {
+                store.save(db)
+            } else {
+                store.save_changes(previous, db)
             }
-            result.push(c);
-            char_count += 1;
         }
-        result.push_str("...");
-        result
+        #[cfg(not(feature = "sqlite"))]
+        Backend::Sqlite => Err(no_sqlite_support()),
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+/// Loads the cache kept by `backend`, first refreshing it from the wiki if
+/// any category is older than `max_age` (never when `offline`).
+async fn load_fresh_database(
+    backend: Backend,
+    scraper: &WikiScraper,
+    cache_path: &Path,
+    max_age: chrono::Duration,
+    offline: bool,
+    verbose: bool,
+) -> Result<ModDatabase> {
+    if backend == Backend::Json {
+        return if offline {
+            ModDatabase::load_offline(cache_path)
+        } else {
+            ModDatabase::ensure_fresh_with_verbosity(scraper, cache_path, max_age, verbose).await
+        };
+    }
     
-    match cli.command {
-        Commands::Update => {
-            println!("🔄 Updating mod database from wiki...");
-            let scraper = WikiScraper::new();
-            let db = scraper.update_database().await?;
-            db.save()?;
-            println!("✅ Database updated with {} mods", db.mods.len());
+    let db = load_database(backend, cache_path)?;
+    if offline {
+        if db.mods.is_empty() {
+            eprintln!("⚠️  No cached mod database found; run 'balatro-wiki update' while online");
         }
-        _ => {
-            let scraper = WikiScraper::new();
-            let db = ModDatabase::ensure_fresh_silent(&scraper).await?;
-            
-            match cli.command {
-                Commands::Browse { category } => {
-                    browse_mods(&db, category).await?;
-                }
-                Commands::Search { query } => {
-                    search_mods(&db, &query)?;
-                }
-                Commands::Info { name } => {
-                    show_mod_info(&db, &name)?;
+        return Ok(db);
+    }
+    if !db.should_update(max_age) {
+        return Ok(db);
+    }
+    if verbose {
+        println!("🔄 Updating mod database...");
+    }
+    let mut fresh = scraper.update_database_with_verbosity(verbose).await?;
+    fresh.carry_over_timestamps(&db);
+    save_database(&db, &fresh, backend, cache_path)?;
+    if verbose {
+        println!("✅ Database updated with {} mods", fresh.mods.len());
+    }
+    Ok(fresh)
+}
+
+/// Loads only the part of the SQLite cache that `command` reads: the one
+/// mod `info` shows, the mods a substring `search` can match, or mod names
+/// for `count`. Returns `None` when the command needs the whole database, or
+/// when the cache is empty or due for a refresh (never when `offline`).
+#[cfg(feature = "sqlite")]
+fn load_sqlite_subset(
+    command: &Commands,
+    cache_path: &Path,
+    max_age: chrono::Duration,
+    offline: bool,
+) -> Result<Option<ModDatabase>> {
+    if !matches!(
+        command,
+        Commands::Info { diff_against_live: false, .. }
+            | Commands::Search { regex: false, semantic: false, .. }
+            | Commands::Count { .. }
+    ) {
+        return Ok(None);
+    }
+    let store = SqliteStore::open(&SqliteStore::path_for(cache_path))?;
+    let names = store.load_names()?;
+    if names.mods.is_empty() || (!offline && names.should_update(max_age)) {
+        return Ok(None);
+    }
+    let db = match command {
+        Commands::Info { name, .. } => store.load_named(name)?,
+        Commands::Search { query, .. } => store.load_matching(query)?,
+        _ => names,
+    };
+    Ok(Some(db))
+}
+
+fn no_sqlite_support() -> anyhow::Error {
+    anyhow!("This build has no SQLite support; rebuild with `--features sqlite` or use --backend json")
+}
+
+/// Loads the saved TF-IDF index, rebuilding it in memory if it is missing or
+/// was built from an older database.
+fn load_or_build_index(db: &ModDatabase, index_path: &Path) -> Result<TfIdfIndex> {
+    match TfIdfIndex::load(index_path)? {
+        Some(index) if index.is_current(db) => Ok(index),
+        _ => Ok(TfIdfIndex::build(db)),
+    }
+}
+
+/// Prints the mods most similar to `query` and returns how many there were.
+fn semantic_search_mods(
+    db: &ModDatabase,
+    index: &TfIdfIndex,
+    query: &str,
+    opts: &SearchOpts,
+    view: &SearchView,
+) -> Result<usize> {
+    let mut matches: Vec<(&ModInfo, f64)> = index.query(query)
+        .into_iter()
+        .filter_map(|(name, similarity)| db.mods.get(&name).map(|mod_info| (mod_info, similarity)))
+        .filter(|(mod_info, _)| opts.accepts(mod_info))
+        .collect();
+    let hidden = hide_without_github(&mut matches, view.only_with_github, |(mod_info, _)| mod_info);
+    top_results(&mut matches, view.sort, view.reverse, |(mod_info, _)| mod_info);
+    let mods: Vec<&ModInfo> = matches.iter().map(|(mod_info, _)| *mod_info).collect();
+    
+    match view.output {
+        OutputFormat::Tsv => print_tsv(&mods, &view.tsv_columns),
+        OutputFormat::Html => println!("{}", render_html(&format!("Balatro Mods related to '{}'", query), &mods)?),
+        OutputFormat::Table => print_table(&mods),
+        OutputFormat::Json => print_json(&mods, &view.json_fields)?,
+        OutputFormat::Names => print_names(&mods),
+        OutputFormat::Text if matches.is_empty() => match hidden {
+            Some(hidden) if hidden > 0 => println!("No mods found related to '{}' ({} hidden without GitHub)", query, hidden),
+            _ => println!("No mods found related to '{}'", query),
+        },
+        OutputFormat::Text => {
+            println!("🧠 Semantic results for '{}' ({} matches{}):", query, matches.len(), hidden_note(hidden));
+            println!("{}", "─".repeat(50));
+            
+            let fields = FieldSet::new(SEARCH_CARD_FIELDS);
+            for (mod_info, similarity) in &matches {
+                println!("📊 Similarity {:.2}", similarity);
+                print_mod_card(mod_info, &fields, None, view.desc_len);
+            }
+        }
+    }
+    Ok(matches.len())
+}
+
+/// Search matches to emphasize in card output, limited to `fields`.
+struct Highlight {
+    pattern: Regex,
+    fields: FieldSet,
+    /// Cut descriptions around their first match instead of from the start.
+    snippets: bool,
+}
+
+impl Highlight {
+    /// Highlights what `search` matched; a no-op when color is off unless
+    /// `snippets` is set.
+    fn for_search(query: &str, opts: &SearchOpts, fields: &[Field], snippets: bool) -> Option<Self> {
+        let pattern = if opts.regex {
+            RegexBuilder::new(query).case_insensitive(opts.ignore_case).build().ok()?
+        } else {
+            let words: Vec<String> = query.split_whitespace().map(regex::escape).collect();
+            RegexBuilder::new(&words.join("|")).case_insensitive(true).build().ok()?
+        };
+        let fields = if fields.is_empty() { FieldSet::new(Field::value_variants()) } else { FieldSet::new(fields) };
+        Some(Self { pattern, fields, snippets })
+    }
+    
+    /// Returns the part of `description` around the first match, with every
+    /// match marked, or None if the description doesn't match or isn't
+    /// highlighted.
+    fn description_snippet(&self, description: &str, desc_len: usize) -> Option<String> {
+        if !self.snippets || !self.fields.contains(Field::Description) {
+            return None;
+        }
+        let found = self.pattern.find_iter(description).find(|m| !m.is_empty())?;
+        Some(mark_matches(&snippet(description, found.range(), desc_len), &self.pattern))
+    }
+}
+
+/// Wraps each match of `pattern` in `text` in bold, or in `**` when color
+/// is off so the match still stands out.
+fn mark_matches(text: &str, pattern: &Regex) -> String {
+    let color = styled(text, MATCH_STYLE) != text;
+    pattern.replace_all(text, |caps: &regex::Captures| match &caps[0] {
+        "" => String::new(),
+        found if color => styled(found, MATCH_STYLE),
+        found => format!("**{}**", found),
+    }).into_owned()
+}
+
+/// Styles `text` with `base`, except that matches of `pattern` are bold
+/// yellow if `field` is one of `fields`.
+fn highlight(text: &str, field: Field, base: Style, pattern: &Regex, fields: &FieldSet) -> String {
+    if !fields.contains(field) {
+        return styled(text, base);
+    }
+    let mut output = String::new();
+    let mut last = 0;
+    for found in pattern.find_iter(text).filter(|m| !m.is_empty()) {
+        output.push_str(&styled(&text[last..found.start()], base));
+        output.push_str(&styled(found.as_str(), MATCH_STYLE));
+        last = found.end();
+    }
+    output.push_str(&styled(&text[last..], base));
+    output
+}
+
+const NAME_STYLE: Style = Style::new().bold().cyan();
+const CATEGORY_STYLE: Style = Style::new().yellow();
+const URL_STYLE: Style = Style::new().blue().underline();
+const MATCH_STYLE: Style = Style::new().bold().yellow();
+
+/// Applies `style` to `text` when stdout supports color (see `--no-color`).
+fn styled(text: &str, style: Style) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    text.if_supports_color(Stream::Stdout, |t| t.style(style)).to_string()
+}
+
+/// Turns color off for the whole run when asked to, or when stdout isn't a
+/// terminal. `NO_COLOR` is honored only if set to a non-empty value.
+fn configure_color(no_color: bool) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if no_color || no_color_env || !std::io::stdout().is_terminal() {
+        owo_colors::set_override(false);
+    }
+}
+
+/// Characters of a mod's description shown on a card unless `--desc-len` says otherwise.
+const DEFAULT_DESC_LEN: usize = 300;
+
+/// Prints a mod's card, cutting its description to `desc_len` characters
+/// (0 for no limit).
+fn print_mod_card(mod_info: &ModInfo, fields: &FieldSet, hl: Option<&Highlight>, desc_len: usize) {
+    let show = |field: Field, text: &str, base: Style| match hl {
+        Some(hl) => highlight(text, field, base, &hl.pattern, &hl.fields),
+        None => styled(text, base),
+    };
+    
+    if fields.contains(Field::Name) {
+        println!("🃏 {}", show(Field::Name, &mod_info.name, NAME_STYLE));
+    }
+    if fields.contains(Field::Category) {
+        println!("   📁 {}", show(Field::Category, &mod_info.category_label(), CATEGORY_STYLE));
+    }
+    if fields.contains(Field::Description) {
+        match hl.and_then(|hl| hl.description_snippet(&mod_info.description, desc_len)) {
+            Some(snippet) => println!("   {}", snippet),
+            None => {
+                let description = match desc_len {
+                    0 => mod_info.description.clone(),
+                    len => truncate(&mod_info.description, len),
+                };
+                println!("   {}", show(Field::Description, &description, Style::new()));
+            }
+        }
+    }
+    if fields.contains(Field::Author) {
+        if let Some(author) = &mod_info.author {
+            println!("   👤 by {}", show(Field::Author, author, Style::new()));
+        }
+    }
+    if fields.contains(Field::Version) {
+        if let Some(version) = &mod_info.version {
+            println!("   📦 {}", version);
+        }
+    }
+    if fields.contains(Field::GithubUrl) {
+        if let Some(github) = &mod_info.github_url {
+            println!("   🔗 {}", styled(github, URL_STYLE));
+        }
+    }
+    if fields.contains(Field::WikiUrl) {
+        println!("   🌐 {}", styled(&mod_info.wiki_url, URL_STYLE));
+    }
+    if fields.contains(Field::Dependencies) && !mod_info.dependencies.is_empty() {
+        println!("   🧩 Depends on: {}", mod_info.dependencies.join(", "));
+    }
+    println!();
+}
+
+fn show_mod_info(db: &ModDatabase, name: &str) -> Result<()> {
+    let mod_info = db.find_normalized(name)
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    
+    print_mod_info(mod_info);
+    Ok(())
+}
+
+/// Fills `{field}` placeholders in `template` from `mod_info` and expands
+/// `\t`/`\n` escapes. Unknown placeholders are left as-is.
+fn format_mod(template: &str, mod_info: &ModInfo) -> String {
+    let fields = [
+        Field::Name,
+        Field::Description,
+        Field::Author,
+        Field::Version,
+        Field::GithubUrl,
+        Field::WikiUrl,
+        Field::Category,
+    ];
+    let mut output = template.replace("\\t", "\t").replace("\\n", "\n");
+    for field in fields {
+        output = output.replace(&format!("{{{}}}", field.header()), &field.value(mod_info));
+    }
+    output
+}
+
+fn print_mod_info(mod_info: &ModInfo) {
+    println!("🃏 {}", styled(&mod_info.name, NAME_STYLE));
+    println!("{}", "═".repeat(50));
+    if mod_info.categories.len() > 1 {
+        println!("📁 Categories: {}", styled(&mod_info.category_label(), CATEGORY_STYLE));
+    } else {
+        println!("📁 Category: {}", styled(&mod_info.category_label(), CATEGORY_STYLE));
+    }
+    if !mod_info.tags.is_empty() {
+        println!("🏷️  Tags: {}", styled(&mod_info.tags.join(", "), CATEGORY_STYLE));
+    }
+    println!("📝 Description: {}", mod_info.description);
+    if !mod_info.features.is_empty() {
+        println!("✨ Features:");
+        for feature in &mod_info.features {
+            println!("   • {}", feature);
+        }
+    }
+    
+    if let Some(author) = &mod_info.author {
+        println!("👤 Author: {}", author);
+    }
+    
+    if let Some(version) = &mod_info.version {
+        println!("📦 Version: {}", version);
+    }
+    
+    if let Some(kudos) = mod_info.kudos_count {
+        println!("👍 Kudos: {}", kudos);
+    }
+    
+    if let Some(stars) = mod_info.stars {
+        println!("⭐ Stars: {}", stars);
+    }
+    
+    if let Some(edited) = &mod_info.last_edited {
+        println!("✏️  Last edited: {}", edited);
+    }
+    
+    if let Some(github) = &mod_info.github_url {
+        println!("🔗 GitHub: {}", styled(github, URL_STYLE));
+        println!("\n💾 To install this mod:");
+        println!("   balatro-install-mod {}", github);
+    }
+    
+    if !mod_info.download_urls.is_empty() {
+        println!("⬇️  Downloads:");
+        for url in &mod_info.download_urls {
+            println!("   {}", styled(url, URL_STYLE));
+        }
+    }
+    
+    println!("🌐 Wiki: {}", styled(&mod_info.wiki_url, URL_STYLE));
+    
+    if let Some(image) = &mod_info.image_url {
+        println!("🖼️  Image: {}", image);
+    }
+    
+    if !mod_info.dependencies.is_empty() {
+        println!("🔗 Dependencies: {}", mod_info.dependencies.join(", "));
+    }
+}
+
+/// Opens `target` for the named mod. A missing GitHub link is reported and
+/// nothing is opened.
+fn open_mod_page(db: &ModDatabase, name: &str, target: OpenTarget) -> Result<()> {
+    let mod_info = db.find_normalized(name)
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    
+    let url = match (target, &mod_info.github_url) {
+        (OpenTarget::Wiki, _) => &mod_info.wiki_url,
+        (OpenTarget::Github, Some(github_url)) => github_url,
+        (OpenTarget::Github, None) => {
+            println!("{} has no GitHub link to open", mod_info.name);
+            return Ok(());
+        }
+    };
+    open::that(url).map_err(|e| anyhow!("Failed to open {} in a browser: {}", url, e))
+}
+
+fn analyze_mod(db: &ModDatabase, name: &str) -> Result<()> {
+    let mod_info = db.find_normalized(name)
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    
+    print_mod_info(mod_info);
+    
+    println!("\n📈 Popularity");
+    println!("{}", "─".repeat(50));
+    match star_rank(db, mod_info) {
+        Some((rank, ranked)) => println!(
+            "   #{} of {} starred mods in its categories (⭐ {})",
+            rank,
+            ranked,
+            mod_info.stars.unwrap_or_default()
+        ),
+        None => println!("   unranked: no GitHub star count is cached"),
+    }
+    
+    println!("\n🌳 Dependency tree");
+    println!("{}", "─".repeat(50));
+    let tree = dependency_tree(db, mod_info);
+    if tree.children.is_empty() {
+        println!("   No dependencies");
+    } else {
+        println!("   {}", tree.name);
+        for child in &tree.children {
+            print_dependency_node(child, 1);
+        }
+    }
+    
+    println!("\n🔙 Depended on by");
+    println!("{}", "─".repeat(50));
+    let dependents = dependents(db, &mod_info.name);
+    if dependents.is_empty() {
+        println!("   No mods depend on {}", mod_info.name);
+    } else {
+        for dependent in dependents {
+            println!("   🃏 {} ({})", dependent.name, dependent.category_label());
+        }
+    }
+    
+    println!("\n🔮 Similar mods");
+    println!("{}", "─".repeat(50));
+    let similar = similar_mods(db, mod_info, 5);
+    if similar.is_empty() {
+        println!("   No similar mods found");
+    } else {
+        for (other, similarity) in similar {
+            println!("   🃏 {} ({:.0}% similar)", other.name, similarity * 100.0);
+        }
+    }
+    
+    Ok(())
+}
+
+type CompareRow = (&'static str, fn(&ModInfo) -> String);
+
+fn compare_mods(db: &ModDatabase, names: &[String]) {
+    let columns: Vec<(String, Option<&ModInfo>)> = names.iter()
+        .map(|name| match db.resolve(name) {
+            Some(mod_info) => (mod_info.name.clone(), Some(mod_info)),
+            None => (format!("❌ {}", name), None),
+        })
+        .collect();
+    
+    let rows: [CompareRow; 5] = [
+        ("Category", |m| m.category_label()),
+        ("Author", |m| m.author.clone().unwrap_or_else(|| "-".to_string())),
+        ("Version", |m| m.version.clone().unwrap_or_else(|| "-".to_string())),
+        ("Dependencies", |m| m.dependencies.len().to_string()),
+        ("GitHub", |m| if m.github_url.is_some() { "yes" } else { "no" }.to_string()),
+    ];
+    let table: Vec<Vec<String>> = rows.iter()
+        .map(|(_, value)| columns.iter()
+            .map(|(_, mod_info)| mod_info.map(value).unwrap_or_else(|| "not found".to_string()))
+            .collect())
+        .collect();
+    
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = columns.iter()
+        .enumerate()
+        .map(|(i, (header, _))| table.iter()
+            .map(|row| row[i].chars().count())
+            .chain(std::iter::once(header.chars().count()))
+            .max()
+            .unwrap_or(0))
+        .collect();
+    
+    let header: Vec<String> = columns.iter()
+        .zip(&widths)
+        .map(|((header, _), &width)| format!("{:width$}", header))
+        .collect();
+    println!("{:label_width$}  {}", "", header.join("  ").trim_end());
+    println!("{}", "─".repeat(label_width + widths.iter().map(|w| w + 2).sum::<usize>()));
+    for ((label, _), row) in rows.iter().zip(&table) {
+        let cells: Vec<String> = row.iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell))
+            .collect();
+        println!("{:label_width$}  {}", label, cells.join("  ").trim_end());
+    }
+}
+
+fn print_dependency_node(node: &DependencyNode, depth: usize) {
+    let marker = if !node.found {
+        " (not in database)"
+    } else if node.cycle {
+        " (circular)"
+    } else {
+        ""
+    };
+    println!("   {}└─ {}{}", "   ".repeat(depth - 1), node.name, marker);
+    for child in &node.children {
+        print_dependency_node(child, depth + 1);
+    }
+}
+
+fn print_tsv(mods: &[&ModInfo], columns: &[Field]) {
+    let header: Vec<&str> = columns.iter().map(|f| f.header()).collect();
+    println!("{}", header.join("\t"));
+    
+    for mod_info in mods {
+        let row: Vec<String> = columns.iter()
+            .map(|f| escape_tsv(&f.value(mod_info)))
+            .collect();
+        println!("{}", row.join("\t"));
+    }
+}
+
+const HTML_TEMPLATE: &str = include_str!("templates/mods.html");
+
+fn render_html(title: &str, mods: &[&ModInfo]) -> Result<String> {
+    let mut env = Environment::new();
+    env.add_template("mods.html", HTML_TEMPLATE)?;
+    
+    let mods: Vec<_> = mods.iter()
+        .map(|mod_info| context! {
+            name => mod_info.name,
+            category => mod_info.category_label(),
+            author => mod_info.author,
+            description => mod_info.description,
+            github_url => mod_info.github_url,
+            wiki_url => mod_info.wiki_url,
+        })
+        .collect();
+    Ok(env.get_template("mods.html")?.render(context! { title, mods })?)
+}
+
+const DOSSIER_TEMPLATE: &str = include_str!("templates/dossier.md");
+const DOSSIER_INDEX_TEMPLATE: &str = include_str!("templates/dossier_index.md");
+
+/// Writes a Markdown dossier for each exported mod into `dir`, named by its
+/// slug, and an `index.md` table linking them all.
+fn export_dossiers(db: &ModDatabase, dir: &Path, since: Option<DateTime<Utc>>) -> Result<()> {
+    let mods = mods_to_export(db, since);
+    let slugs = dossier_slugs(&mods);
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.add_template("dossier.md", DOSSIER_TEMPLATE)?;
+    env.add_template("index.md", DOSSIER_INDEX_TEMPLATE)?;
+    std::fs::create_dir_all(dir)?;
+    
+    for mod_info in &mods {
+        let mut details = vec![("Category", mod_info.category_label())];
+        if !mod_info.tags.is_empty() {
+            details.push(("Tags", mod_info.tags.join(", ")));
+        }
+        let optional = [
+            ("Author", mod_info.author.clone()),
+            ("Version", mod_info.version.clone()),
+            ("Kudos", mod_info.kudos_count.map(|kudos| kudos.to_string())),
+            ("GitHub stars", mod_info.stars.map(|stars| stars.to_string())),
+            ("Last updated", mod_info.last_updated.clone()),
+        ];
+        details.extend(optional.into_iter().filter_map(|(label, value)| value.map(|value| (label, value))));
+        let details: Vec<(&str, String)> = details.into_iter().map(|(label, value)| (label, md_cell(&value))).collect();
+        let dependencies: Vec<_> = mod_info.dependencies.iter()
+            .map(|name| context! {
+                name => name,
+                slug => db.find(name).and_then(|dependency| slugs.get(dependency.name.as_str())),
+            })
+            .collect();
+        
+        let page = env.get_template("dossier.md")?.render(context! {
+            name => mod_info.name,
+            description => mod_info.description,
+            features => mod_info.features,
+            details => details,
+            dependencies => dependencies,
+            wiki_url => mod_info.wiki_url,
+            github_url => mod_info.github_url,
+            download_urls => mod_info.download_urls,
+        })?;
+        std::fs::write(dir.join(format!("{}.md", slugs[mod_info.name.as_str()])), page)?;
+    }
+    
+    let index_rows: Vec<_> = mods.iter()
+        .map(|mod_info| context! {
+            name => md_cell(&mod_info.name),
+            slug => slugs[mod_info.name.as_str()],
+            category => md_cell(&mod_info.category_label()),
+            author => md_cell(mod_info.author.as_deref().unwrap_or("")),
+        })
+        .collect();
+    std::fs::write(dir.join("index.md"), env.get_template("index.md")?.render(context! { mods => index_rows })?)?;
+    
+    eprintln!("✅ Wrote {} mod pages and index.md to {}", mods.len(), dir.display());
+    Ok(())
+}
+
+/// Assigns each mod a unique file slug, numbering repeats in name order.
+/// `index` is reserved for the index page.
+fn dossier_slugs<'a>(mods: &[&'a ModInfo]) -> HashMap<&'a str, String> {
+    let mut taken: HashSet<String> = HashSet::from(["index".to_string()]);
+    let mut slugs = HashMap::new();
+    for mod_info in mods {
+        let base = slugify(&mod_info.name);
+        let mut slug = base.clone();
+        let mut n = 2;
+        while !taken.insert(slug.clone()) {
+            slug = format!("{}-{}", base, n);
+            n += 1;
+        }
+        slugs.insert(mod_info.name.as_str(), slug);
+    }
+    slugs
+}
+
+/// Escapes text for a Markdown table cell or link label.
+fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(['\n', '\r'], " ")
+}
+
+fn escape_tsv(value: &str) -> String {
+    value.replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+async fn diff_mod_against_live(db: &mut ModDatabase, cache_path: &Path, scraper: &WikiScraper, name: &str, update: bool) -> Result<()> {
+    let cached = db.find_normalized(name)
+        .cloned()
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    
+    let mut live = scraper.scrape_mod_page(&cached.name).await?;
+    // Category membership comes from the category listing, not the page itself
+    live.categories = cached.categories.clone();
+    
+    let fields = [
+        Field::Name,
+        Field::Description,
+        Field::Author,
+        Field::Version,
+        Field::GithubUrl,
+        Field::WikiUrl,
+        Field::Dependencies,
+    ];
+    let diffs: Vec<_> = fields.iter()
+        .map(|f| (f, f.value(&cached), f.value(&live)))
+        .filter(|(_, old, new)| old != new)
+        .collect();
+    
+    if diffs.is_empty() {
+        println!("✅ Cache is current");
+        return Ok(());
+    }
+    
+    println!("🔍 Differences for '{}' ({} fields):", cached.name, diffs.len());
+    println!("{}", "─".repeat(50));
+    for (field, old, new) in &diffs {
+        println!("{}:", field);
+        println!("   - {}", old);
+        println!("   + {}", new);
+    }
+    
+    if update {
+        db.mods.remove(&cached.name);
+        if live.name != cached.name {
+            for category in &live.categories {
+                if let Some(cat_mods) = db.categories.get_mut(category) {
+                    for mod_name in cat_mods.iter_mut().filter(|n| **n == cached.name) {
+                        *mod_name = live.name.clone();
+                    }
+                }
+            }
+        }
+        db.mods.insert(live.name.clone(), live);
+        db.clear_search_index();
+        db.save_to(cache_path)?;
+        println!("\n✅ Cache updated");
+    }
+    
+    Ok(())
+}
+
+fn parse_wiki_url(s: &str) -> Result<String, String> {
+    match reqwest::Url::parse(s) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {
+            Ok(url.as_str().trim_end_matches('/').to_string())
+        }
+        _ => Err(format!("'{}' is not an http(s) URL", s)),
+    }
+}
+
+fn parse_user_agent(s: &str) -> Result<String, String> {
+    match reqwest::header::HeaderValue::from_str(s) {
+        Ok(_) => Ok(s.to_string()),
+        Err(_) => Err(format!("'{}' can't be sent as a User-Agent header", s.escape_debug())),
+    }
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!("'{}' is not a positive number of requests per second", s)),
+    }
+}
+
+/// Parses a duration such as `7d`, `48h`, `30m`, or `2w` into the moment
+/// that long ago.
+fn parse_since(s: &str) -> Result<DateTime<Utc>, String> {
+    parse_duration(s).map(|duration| Utc::now() - duration)
+}
+
+/// Parses a duration such as `7d`, `48h`, `30m`, or `2w`. A bare `0` needs no unit.
+fn parse_duration(s: &str) -> Result<chrono::Duration, String> {
+    let invalid = || format!("'{}' is not a duration; use a number followed by m, h, d, or w (e.g. 30m, 48h, 7d, 2w)", s);
+    let s = s.trim();
+    if s == "0" {
+        return Ok(chrono::Duration::zero());
+    }
+    let unit_start = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount: i64 = s[..unit_start].parse().map_err(|_| invalid())?;
+    let duration = match &s[unit_start..] {
+        "m" | "min" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => None,
+    };
+    duration.ok_or_else(invalid)
+}
+
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(s) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .map_err(|_| format!("'{}' is not an RFC 3339 timestamp or YYYY-MM-DD date", s))
+}
+
+/// Serializes `mod_info` as it is stored in the cache, keeping only `fields`
+/// when any are given.
+fn mod_json(mod_info: &ModInfo, fields: &[String]) -> Result<serde_json::Value> {
+    let value = serde_json::to_value(mod_info)?;
+    match value {
+        serde_json::Value::Object(map) if !fields.is_empty() => {
+            Ok(serde_json::Value::Object(map.into_iter().filter(|(key, _)| fields.contains(key)).collect()))
+        }
+        value => Ok(value),
+    }
+}
+
+/// Prints `mods` as a pretty JSON array, projected to `fields` if any are given.
+fn print_json(mods: &[&ModInfo], fields: &[String]) -> Result<()> {
+    let values = mods.iter().map(|mod_info| mod_json(mod_info, fields)).collect::<Result<Vec<_>>>()?;
+    println!("{}", serde_json::to_string_pretty(&values)?);
+    Ok(())
+}
+
+/// The mods to export, sorted by name: all of them, or only those whose
+/// data changed after `since`.
+fn mods_to_export(db: &ModDatabase, since: Option<DateTime<Utc>>) -> Vec<&ModInfo> {
+    let mut mods: Vec<&ModInfo> = db.mods.values()
+        .filter(|mod_info| match since {
+            Some(since) => mod_info.last_updated.as_deref()
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .is_some_and(|ts| ts.with_timezone(&Utc) > since),
+            None => true,
+        })
+        .collect();
+    mods.sort_by(|a, b| a.name.cmp(&b.name));
+    mods
+}
+
+fn export_mods(db: &ModDatabase, output: Option<&Path>, since: Option<DateTime<Utc>>, fields: &[String]) -> Result<()> {
+    let mods = mods_to_export(db, since);
+    let values = mods.iter().map(|mod_info| mod_json(mod_info, fields)).collect::<Result<Vec<_>>>()?;
+    let content = serde_json::to_string_pretty(&values)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            eprintln!("✅ Exported {} mods to {}", mods.len(), path.display());
+        }
+        None => println!("{}", content),
+    }
+    Ok(())
+}
+
+/// Prints the last recorded change set, unless it is older than `since`.
+fn show_changes(changes_path: &Path, since: Option<DateTime<Utc>>) -> Result<()> {
+    let Some(changes) = ChangeSet::load(changes_path)? else {
+        println!("No update has been recorded yet. Run 'balatro-wiki update' first");
+        return Ok(());
+    };
+    if let Some(since) = since {
+        let updated = DateTime::parse_from_rfc3339(&changes.updated_at).map(|t| t.with_timezone(&Utc));
+        if updated.is_ok_and(|updated| updated < since) {
+            println!("No changes since {} (the last update ran at {})", since.to_rfc3339_opts(SecondsFormat::Secs, true), changes.updated_at);
+            return Ok(());
+        }
+    }
+    
+    println!("📰 Changes from the update at {}", changes.updated_at);
+    println!("{}", "─".repeat(50));
+    if changes.is_empty() {
+        println!("   Nothing changed");
+        return Ok(());
+    }
+    
+    if !changes.added.is_empty() {
+        println!("➕ Added ({}):", changes.added.len());
+        for name in &changes.added {
+            println!("   {}", name);
+        }
+    }
+    if !changes.removed.is_empty() {
+        println!("➖ Removed ({}):", changes.removed.len());
+        for name in &changes.removed {
+            println!("   {}", name);
+        }
+    }
+    if !changes.version_changed.is_empty() {
+        println!("🔼 Version changed ({}):", changes.version_changed.len());
+        for change in &changes.version_changed {
+            println!("   {}: {} → {}",
+                     change.name,
+                     change.old.as_deref().unwrap_or("unknown"),
+                     change.new.as_deref().unwrap_or("unknown"));
+        }
+    }
+    Ok(())
+}
+
+/// Prints the newest mod pages, optionally only those edited after `since`,
+/// reusing `recent.json` while it is fresh. Without a scraper (offline), the
+/// cached list is shown however old it is.
+async fn show_recent_mods(
+    db: &ModDatabase,
+    cache_path: &Path,
+    scraper: Option<&WikiScraper>,
+    limit: usize,
+    since: Option<DateTime<Utc>>,
+    verbose: bool,
+) -> Result<()> {
+    let recent_path = RecentMods::path_for(cache_path);
+    let cached = RecentMods::load(&recent_path);
+    
+    let recent = match (cached, scraper) {
+        (Some(cached), _) if cached.is_fresh_for(limit, since) => cached,
+        (Some(cached), None) => cached,
+        (None, None) => return Err(anyhow!("No recent mods cached. Run 'balatro-wiki recent' while online first")),
+        (_, Some(scraper)) => {
+            let recent = RecentMods::new(scraper.recent_mods(db, limit, since, verbose).await?, limit, since);
+            recent.save(&recent_path)?;
+            recent
+        }
+    };
+    let mods: Vec<&ModInfo> = match since {
+        Some(since) => recent.edited_since(since).take(limit).collect(),
+        None => recent.mods.iter().take(limit).collect(),
+    };
+    
+    match since {
+        Some(since) => println!("🆕 Mods created or edited since {} (as of {}):", since.to_rfc3339_opts(SecondsFormat::Secs, true), recent.fetched_at),
+        None => println!("🆕 Recently created or edited mods (as of {}):", recent.fetched_at),
+    }
+    println!("{}", "─".repeat(50));
+    if mods.is_empty() {
+        println!("   No recent mod pages found");
+        return Ok(());
+    }
+    
+    let fields = FieldSet::new(BROWSE_CARD_FIELDS);
+    for mod_info in mods {
+        print_mod_card(mod_info, &fields, None, DEFAULT_DESC_LEN);
+    }
+    Ok(())
+}
+
+fn add_favorite(db: &ModDatabase, favorites_path: &Path, name: &str) -> Result<()> {
+    let mod_info = db.find(name)
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    
+    let mut favorites = Bookmarks::load(favorites_path)?;
+    if favorites.insert(&mod_info.name) {
+        favorites.save(favorites_path)?;
+        println!("⭐ Added '{}' to favorites", mod_info.name);
+    } else {
+        println!("'{}' is already a favorite", mod_info.name);
+    }
+    Ok(())
+}
+
+fn remove_favorite(favorites_path: &Path, name: &str) -> Result<()> {
+    let mut favorites = Bookmarks::load(favorites_path)?;
+    if !favorites.remove(name) {
+        return Err(anyhow!("'{}' is not a favorite", name));
+    }
+    favorites.save(favorites_path)?;
+    println!("✅ Removed '{}' from favorites", name);
+    Ok(())
+}
+
+fn list_favorites(db: &ModDatabase, favorites_path: &Path) -> Result<()> {
+    let favorites = Bookmarks::load(favorites_path)?;
+    if favorites.iter().next().is_none() {
+        println!("No favorites yet. Add one with 'balatro-wiki fav <name>'");
+        return Ok(());
+    }
+    
+    println!("⭐ Favorite Mods ({}):", favorites.iter().count());
+    println!("{}", "─".repeat(50));
+    
+    let fields = FieldSet::new(BROWSE_CARD_FIELDS);
+    for name in favorites.iter() {
+        match db.find(name) {
+            Some(mod_info) => print_mod_card(mod_info, &fields, None, DEFAULT_DESC_LEN),
+            None => println!("⚠️  {} (no longer in the database)\n", name),
+        }
+    }
+    Ok(())
+}
+
+/// The flake's installer and remover scripts, run by Install and Uninstall.
+const INSTALLER: &str = "balatro-install-mod";
+const REMOVER: &str = "balatro-remove-mod";
+
+/// Runs `program` with `arg`, failing unless it exits successfully.
+fn run_helper(program: &str, arg: &str) -> Result<()> {
+    let status = std::process::Command::new(program)
+        .arg(arg)
+        .status()
+        .map_err(|e| anyhow!("Could not run {}: {} (see 'balatro-wiki doctor')", program, e))?;
+    if !status.success() {
+        return Err(anyhow!("{} {} failed ({})", program, arg, status));
+    }
+    Ok(())
+}
+
+fn install_mod(db: &ModDatabase, manifest_path: &Path, name: &str, record_only: bool) -> Result<()> {
+    let mod_info = db.resolve(name)
+        .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+    let github_url = mod_info.github_url.as_deref()
+        .ok_or_else(|| anyhow!("'{}' has no GitHub URL to install from", mod_info.name))?;
+    
+    if !record_only {
+        run_helper(INSTALLER, github_url)?;
+    }
+    let mut installed = InstalledMods::load(manifest_path)?;
+    installed.record(InstalledMod::from_mod(mod_info));
+    installed.save(manifest_path)?;
+    println!("📥 Tracking '{}' as installed", mod_info.name);
+    Ok(())
+}
+
+fn list_installed(db: &ModDatabase, manifest_path: &Path) -> Result<()> {
+    let installed = InstalledMods::load(manifest_path)?;
+    if installed.is_empty() {
+        println!("No installed mods tracked yet. Install one with 'balatro-wiki install <name>'");
+        return Ok(());
+    }
+    
+    println!("💾 Installed Mods ({}):", installed.iter().count());
+    println!("{}", "─".repeat(50));
+    for entry in installed.iter() {
+        let version = entry.version.as_deref().unwrap_or("unknown version");
+        match db.find(&entry.name) {
+            None => println!("⚠️  {} ({}) is no longer on the wiki", entry.name, version),
+            Some(current) if current.version.is_some() && current.version != entry.version => {
+                println!("🔼 {} ({} → {} available)", entry.name, version, current.version.as_deref().unwrap_or(""));
+            }
+            Some(_) => println!("✅ {} ({})", entry.name, version),
+        }
+    }
+    Ok(())
+}
+
+fn uninstall_mod(manifest_path: &Path, name: &str, remove_files: bool) -> Result<()> {
+    let mut installed = InstalledMods::load(manifest_path)?;
+    let entry = installed.remove(name)
+        .ok_or_else(|| anyhow!("'{}' is not a tracked installed mod", name))?;
+    
+    if remove_files {
+        let directory = entry.directory.as_deref()
+            .ok_or_else(|| anyhow!("Don't know which folder '{}' was installed to", entry.name))?;
+        run_helper(REMOVER, directory)?;
+    }
+    installed.save(manifest_path)?;
+    println!("✅ Stopped tracking '{}'", entry.name);
+    Ok(())
+}
+
+/// Polls `category` every `interval` and scrapes mods that join it, saving
+/// the database and `http_cache` after each poll. The wiki category polled
+/// is the one `categories` configures for `category`.
+async fn watch_category(
+    db: &mut ModDatabase,
+    cache_path: &Path,
+    scraper: &WikiScraper,
+    http_cache: &HttpCache,
+    categories: &Categories,
+    category: &str,
+    interval: Duration,
+) -> Result<()> {
+    let wiki_category = categories.wiki_category(category).unwrap_or(category);
+    let mut known: Vec<String> = match db.categories.get(category) {
+        Some(mod_names) => mod_names.clone(),
+        None => scraper.scrape_category_page_all(wiki_category).await?,
+    };
+    
+    println!("👀 Watching '{}' ({} mods known), polling every {}. Press Ctrl-C to stop.",
+             category, known.len(), describe_interval(interval));
+    
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+        
+        let current = match scraper.scrape_category_page_all(wiki_category).await {
+            Ok(current) => current,
+            Err(e) => {
+                eprintln!("⚠️  Failed to poll '{}': {}", category, e);
+                continue;
+            }
+        };
+        
+        for mod_name in current.iter().filter(|name| !known.contains(name)) {
+            match scraper.scrape_mod_page(mod_name).await {
+                Ok(mut mod_info) => {
+                    println!("[NEW MOD] {} — {}", mod_info.name, truncate(&mod_info.description, 120));
+                    mod_info.categories = vec![category.to_string()];
+                    let cat_mods = db.categories.entry(category.to_string()).or_default();
+                    if !cat_mods.contains(&mod_info.name) {
+                        cat_mods.push(mod_info.name.clone());
+                    }
+                    db.mods.entry(mod_info.name.clone()).or_insert(mod_info);
+                    db.clear_search_index();
+                }
+                Err(e) => {
+                    println!("[NEW MOD] {} (failed to scrape page: {})", mod_name, e);
+                }
+            }
+        }
+        
+        if current.iter().any(|name| !known.contains(name)) {
+            db.save_to(cache_path)?;
+        }
+        http_cache.save(&HttpCache::path_for(cache_path))?;
+        known = current;
+    }
+    
+    println!("👋 Stopped watching '{}'", category);
+    Ok(())
+}
+
+/// Refreshes the whole database every `interval` and reports mods it didn't
+/// have before, recording each change set like `update` does, and saves
+/// `http_cache` pruned to the pages of the latest refresh. Failed refreshes
+/// are logged and retried on the next poll.
+async fn watch_wiki(
+    mut db: ModDatabase,
+    cache_path: &Path,
+    scraper: &WikiScraper,
+    http_cache: &HttpCache,
+    interval: Duration,
+    verbose: bool,
+) -> Result<()> {
+    // Mods reported this session, so one that briefly drops off isn't announced again
+    let mut seen: HashSet<String> = db.mods.keys().cloned().collect();
+    println!("👀 Watching the wiki ({} mods known), polling every {}. Press Ctrl-C to stop.",
+             seen.len(), describe_interval(interval));
+    
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+        
+        let refreshed = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            refreshed = scraper.update_database_with_verbosity(verbose) => refreshed,
+        };
+        let mut fresh = match refreshed {
+            Ok(fresh) if !fresh.mods.is_empty() => fresh,
+            Ok(_) => {
+                eprintln!("⚠️  Refresh found no mods; keeping the previous snapshot");
+                continue;
+            }
+            Err(e) => {
+                eprintln!("⚠️  Failed to refresh the database: {}", e);
+                continue;
+            }
+        };
+        fresh.carry_over_timestamps(&db);
+        
+        let changes = ChangeSet::between(&db, &fresh);
+        for name in &changes.added {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            if let Some(mod_info) = fresh.mods.get(name) {
+                println!("[NEW MOD] {} — {}", mod_info.name, truncate(&mod_info.description, 120));
+            }
+        }
+        if !changes.is_empty() {
+            changes.save(&ChangeSet::path_for(cache_path))?;
+        }
+        fresh.save_to(cache_path)?;
+        http_cache.prune_unused();
+        http_cache.save(&HttpCache::path_for(cache_path))?;
+        db = fresh;
+    }
+    
+    println!("👋 Stopped watching");
+    Ok(())
+}
+
+/// Formats a poll interval as whole minutes when it is one, else seconds.
+fn describe_interval(interval: Duration) -> String {
+    let secs = interval.as_secs();
+    if secs.is_multiple_of(60) {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Summarizes an update that was not saved: mods found per category and,
+/// when known, how many mod pages failed to scrape or were skipped.
+fn print_dry_run_report(db: &ModDatabase, crawl: Option<&UpdateProgress>) {
+    println!("🧪 Dry run: found {} mods (cache not modified)", db.mods.len());
+    println!("{}", "─".repeat(50));
+    let mut categories: Vec<(&String, &Vec<String>)> = db.categories.iter().collect();
+    categories.sort();
+    for (category, mods) in categories {
+        println!("🗂️  {} ({} mods)", category, mods.len());
+    }
+    if let Some(crawl) = crawl {
+        println!("❌ Failed to scrape: {}", crawl.failed);
+        println!("⏭️  Skipped non-mod pages: {}", crawl.skipped);
+        println!("♻️  Unchanged pages reused: {}", crawl.unchanged);
+    }
+}
+
+fn write_sitemap(db: &ModDatabase, path: &Path) -> Result<()> {
+    std::fs::write(path, sitemap_xml(db))?;
+    println!("🗺️  Wrote sitemap of {} mods to {}", db.mods.len(), path.display());
+    Ok(())
+}
+
+/// Formats `update --timings` output: phase durations, per-mod average and
+/// median, and the slowest mod pages.
+fn format_timings(timings: &UpdateTimings) -> String {
+    let secs = |duration: Duration| format!("{:>8.2}s", duration.as_secs_f64());
+    let mut lines = vec![
+        "⏱️  Update timings".to_string(),
+        "─".repeat(50),
+        format!("{:<24}{}", "Category listings", secs(timings.categories)),
+        format!("{:<24}{}", "Mod pages (wall time)", secs(timings.mod_pages)),
+    ];
+    if let (Some(average), Some(median)) = (timings.average(), timings.median()) {
+        lines.push(format!("{:<24}{}", "Per mod, average", secs(average)));
+        lines.push(format!("{:<24}{}", "Per mod, median", secs(median)));
+        lines.push("Slowest mod pages:".to_string());
+        for (name, elapsed) in timings.slowest(5) {
+            lines.push(format!("  {} {}", secs(*elapsed), name));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Checks every mod's GitHub link and lists the broken, redirected, and
+/// uncheckable ones, or prints every result as JSON.
+async fn check_links(db: &ModDatabase, scraper: &WikiScraper, concurrency: usize, json: bool) -> Result<()> {
+    let total = db.mods.values().filter(|mod_info| mod_info.github_url.is_some()).count();
+    if !json {
+        println!("🔗 Checking {} GitHub links...", total);
+    }
+    let results = scraper.check_github_links(db, concurrency).await;
+    
+    if json {
+        let entries: Vec<serde_json::Value> = results.iter()
+            .map(|(name, url, result)| match result {
+                Ok(LinkStatus::Ok) => serde_json::json!({ "name": name, "url": url, "status": "ok" }),
+                Ok(LinkStatus::Redirected(target)) => {
+                    serde_json::json!({ "name": name, "url": url, "status": "redirected", "redirected_to": target })
+                }
+                Ok(LinkStatus::Broken(code)) => {
+                    serde_json::json!({ "name": name, "url": url, "status": "broken", "http_status": code })
+                }
+                Err(e) => serde_json::json!({ "name": name, "url": url, "status": "error", "error": e.to_string() }),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    
+    let mut ok = 0;
+    let mut broken = Vec::new();
+    let mut redirected = Vec::new();
+    let mut unchecked = Vec::new();
+    for (name, url, result) in &results {
+        match result {
+            Ok(LinkStatus::Ok) => ok += 1,
+            Ok(LinkStatus::Redirected(target)) => redirected.push(format!("{}: {} → {}", name, url, target)),
+            Ok(LinkStatus::Broken(code)) => broken.push(format!("{}: {} ({})", name, url, code)),
+            Err(e) => unchecked.push(format!("{}: {} ({})", name, url, e)),
+        }
+    }
+    
+    for (heading, lines) in [
+        ("❌ Broken", &broken),
+        ("↪️  Redirected", &redirected),
+        ("⚠️  Could not check", &unchecked),
+    ] {
+        if !lines.is_empty() {
+            println!("\n{} ({}):", heading, lines.len());
+            for line in lines {
+                println!("   {}", line);
+            }
+        }
+    }
+    println!("{}", "─".repeat(50));
+    println!("✅ {} ok, ❌ {} broken, ↪️  {} redirected, ⚠️  {} unchecked", ok, broken.len(), redirected.len(), unchecked.len());
+    Ok(())
+}
+
+/// Reports description wikilinks whose target is neither a cached mod nor
+/// an existing wiki page. Titles that can't be checked are reported separately.
+async fn verify_wikilinks(db: &ModDatabase, scraper: &WikiScraper) {
+    let mut mods: Vec<&ModInfo> = db.mods.values().collect();
+    mods.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    let mut checked: HashMap<String, Option<bool>> = HashMap::new();
+    let mut broken = Vec::new();
+    let mut unchecked = Vec::new();
+    let mut total = 0;
+    for mod_info in mods {
+        for title in wikilinks(&mod_info.description) {
+            total += 1;
+            if db.find(&title).is_some() {
+                continue;
+            }
+            let exists = match checked.get(&title) {
+                Some(exists) => *exists,
+                None => {
+                    let exists = scraper.page_exists(&title).await.ok();
+                    checked.insert(title.clone(), exists);
+                    exists
+                }
+            };
+            match exists {
+                Some(true) => {}
+                Some(false) => broken.push((mod_info.name.clone(), title)),
+                None => unchecked.push((mod_info.name.clone(), title)),
+            }
+        }
+    }
+    
+    println!("\n🔗 Checked {} wikilinks", total);
+    if broken.is_empty() {
+        println!("✅ No broken wikilinks");
+    } else {
+        println!("❌ {} broken wikilinks:", broken.len());
+        for (mod_name, title) in &broken {
+            println!("   {}: [[{}]]", mod_name, title);
+        }
+    }
+    if !unchecked.is_empty() {
+        println!("⚠️  {} wikilinks could not be checked:", unchecked.len());
+        for (mod_name, title) in &unchecked {
+            println!("   {}: [[{}]]", mod_name, title);
+        }
+    }
+}
+
+/// Prints one doctor check result. Returns true if a critical check failed.
+fn report_check(passed: bool, critical: bool, label: &str, detail: &str) -> bool {
+    let marker = match (passed, critical) {
+        (true, _) => "✅",
+        (false, true) => "❌",
+        (false, false) => "⚠️ ",
+    };
+    println!("{} {}: {}", marker, label, detail);
+    !passed && critical
+}
+
+async fn run_doctor(cache_path: &Path, scraper: &WikiScraper, max_age: chrono::Duration, offline: bool) -> Result<()> {
+    println!("🩺 balatro-wiki doctor");
+    println!("{}", "─".repeat(50));
+    let mut critical_failures = 0;
+    
+    let cache_dir = cache_path.parent().unwrap_or(Path::new("."));
+    let probe = cache_dir.join(".doctor-probe");
+    let writable = std::fs::create_dir_all(cache_dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    let detail = match &writable {
+        Ok(()) => cache_dir.display().to_string(),
+        Err(e) => format!("{} ({})", cache_dir.display(), e),
+    };
+    critical_failures += report_check(writable.is_ok(), true, "Cache directory writable", &detail) as usize;
+    
+    let (cache_ok, detail) = if !cache_path.exists() {
+        (false, "no cache yet; run 'balatro-wiki update'".to_string())
+    } else {
+        let db = ModDatabase::load_from(cache_path)?;
+        let age = DateTime::parse_from_rfc3339(&db.last_updated)
+            .map(|updated| Utc::now().signed_duration_since(updated.with_timezone(&Utc)));
+        match age {
+            Ok(age) => (
+                !db.mods.is_empty() && age < max_age,
+                format!("{} mods, updated {}h ago", db.mods.len(), age.num_hours()),
+            ),
+            Err(_) => (false, format!("{} mods, unknown age", db.mods.len())),
+        }
+    };
+    report_check(cache_ok, false, "Mod cache", &detail);
+    
+    if offline {
+        println!("⏭️  Wiki reachable: skipped in offline mode");
+    } else {
+        let (reachable, detail) = match scraper.ping().await {
+            Ok(status) if status < 400 => (true, format!("HTTP {}", status)),
+            Ok(status) => (false, format!("HTTP {}", status)),
+            Err(e) => (false, e.to_string()),
+        };
+        critical_failures += report_check(reachable, true, "Wiki reachable", &detail) as usize;
+    }
+    
+    let installer = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).map(|dir| dir.join(INSTALLER)).find(|p| p.is_file()))
+        .unwrap_or_default();
+    let detail = match &installer {
+        Some(path) => path.display().to_string(),
+        None => "not found on PATH".to_string(),
+    };
+    report_check(installer.is_some(), false, INSTALLER, &detail);
+    
+    let has_token = std::env::var("GITHUB_TOKEN").is_ok_and(|t| !t.is_empty());
+    let detail = if has_token { "set" } else { "not set; GitHub lookups use the anonymous rate limit" };
+    report_check(has_token, false, "GITHUB_TOKEN", detail);
+    
+    if critical_failures > 0 {
+        return Err(anyhow!("{} critical check(s) failed", critical_failures));
+    }
+    Ok(())
+}
+
+/// Prints the configured categories in crawl order with their mod counts
+/// and aliases, then any other categories found in the cache.
+fn list_categories(db: &ModDatabase, categories: &Categories) {
+    println!("📂 Available categories:");
+    for def in categories.iter() {
+        let Some(mods) = db.categories.get(&def.name) else {
+            continue;
+        };
+        match def.aliases.as_slice() {
+            [] => println!("  {} ({} mods)", def.name, mods.len()),
+            aliases => println!("  {} ({} mods, alias: {})", def.name, mods.len(), aliases.join(", ")),
+        }
+    }
+    let mut others: Vec<(&String, &Vec<String>)> = db.categories.iter()
+        .filter(|(category, _)| categories.wiki_category(category).is_none())
+        .collect();
+    others.sort();
+    for (category, mods) in others {
+        println!("  {} ({} mods)", category, mods.len());
+    }
+}
+
+/// Failures that scripts need to tell apart from other errors by exit code.
+#[derive(Debug)]
+enum CliError {
+    /// No mod has the requested name.
+    NotFound(String),
+    /// A search ran fine but matched nothing; the command already said so.
+    NoMatches,
+    /// An update saved what it could, but this many categories or mod pages failed.
+    PartialUpdate(usize),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::NotFound(name) => write!(f, "Mod '{}' not found", name),
+            CliError::NoMatches => write!(f, "No mods matched"),
+            CliError::PartialUpdate(failed) => {
+                write!(f, "{} categories or mod pages could not be scraped; the rest was updated", failed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Turns an update's failures into [`CliError::PartialUpdate`], if there were any.
+fn failures_to_result(failures: &UpdateFailures) -> Result<()> {
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::PartialUpdate(failures.len()).into())
+    }
+}
+
+/// How a failed command is reported: its exit code and `--json` error kind.
+#[derive(Clone, Copy)]
+enum ErrorKind {
+    NotFound,
+    Network,
+    PartialUpdate,
+    Other,
+}
+
+impl ErrorKind {
+    /// Classifies `err`: unknown mods and empty searches are not-found,
+    /// anything caused by an HTTP failure is a network error.
+    fn of(err: &anyhow::Error) -> Self {
+        match err.downcast_ref::<CliError>() {
+            Some(CliError::NotFound(_) | CliError::NoMatches) => ErrorKind::NotFound,
+            Some(CliError::PartialUpdate(_)) => ErrorKind::PartialUpdate,
+            None if err.chain().any(|cause| cause.is::<reqwest::Error>()) => ErrorKind::Network,
+            None => ErrorKind::Other,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::Network => "network",
+            ErrorKind::PartialUpdate => "partial_update",
+            ErrorKind::Other => "error",
+        }
+    }
+
+    /// 2 when something wasn't found, 3 for network or scrape failures, 4 when
+    /// an update skipped what it couldn't scrape, and 1 for anything else.
+    fn exit_code(&self) -> u8 {
+        match self {
+            ErrorKind::NotFound => 2,
+            ErrorKind::Network => 3,
+            ErrorKind::PartialUpdate => 4,
+            ErrorKind::Other => 1,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let json = cli.json;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let kind = ErrorKind::of(&err);
+            if json {
+                // One line on stderr, so stdout stays valid JSON for pipelines
+                eprintln!("{}", serde_json::json!({ "error": format!("{:#}", err), "kind": kind.as_str() }));
+            } else if !matches!(err.downcast_ref::<CliError>(), Some(CliError::NoMatches)) {
+                eprintln!("Error: {:?}", err);
+            }
+            ExitCode::from(kind.exit_code())
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    configure_color(cli.no_color);
+    let cache_path = cache_path(cli.profile.as_deref())?;
+    // A broken categories file shouldn't lock every command out of the cache,
+    // but crawling the wrong categories would drop mods from it
+    let categories = match Categories::load(&cache_path) {
+        Ok(categories) => categories,
+        Err(e) if !matches!(cli.command, Commands::Update { .. } | Commands::Watch { .. }) => {
+            eprintln!("⚠️  {:#}; using the built-in categories", e);
+            Categories::default()
+        }
+        Err(e) => return Err(e),
+    };
+    if cli.backend == Backend::Sqlite {
+        if !cfg!(feature = "sqlite") {
+            return Err(no_sqlite_support());
+        }
+        if !matches!(
+            cli.command,
+            Commands::Update { .. }
+                | Commands::Browse { .. }
+                | Commands::Search { .. }
+                | Commands::Info { .. }
+                | Commands::Count { .. }
+                | Commands::Categories
+        ) {
+            return Err(anyhow!(
+                "--backend sqlite is only supported by update, browse, search, info, count, and categories"
+            ));
+        }
+    }
+    let scraper_config = ScraperConfig {
+        timeout: Duration::from_secs(cli.timeout),
+        user_agent: cli.user_agent.clone(),
+        request_delay: Duration::from_millis(cli.request_delay),
+        base_url: cli.wiki_url.clone(),
+        category_page_size: cli.category_page_size,
+        categories: categories.clone(),
+        ..ScraperConfig::default()
+    };
+    
+    match cli.command {
+        Commands::Update {
+            progress_file,
+            rate_limit,
+            category,
+            generate_sitemap,
+            verify_wikipedia_links,
+            since,
+            since_last_run,
+            stream,
+            github_stars,
+            dry_run,
+            timings,
+            refetch_all,
+            strict,
+            quiet_errors,
+        } => {
+            if cli.offline {
+                return Err(anyhow!("Cannot update the mod database in offline mode"));
+            }
+            // Streamed mods own stdout, so progress logging is dropped
+            let verbose = !cli.quiet && !stream;
+            if verbose {
+                println!("🔄 Updating mod database from wiki...");
+            }
+            let http_cache = Arc::new(HttpCache::load(&HttpCache::path_for(&cache_path)));
+            let mut scraper = WikiScraper::from_config(scraper_config.clone())
+                .with_http_cache(http_cache.clone())
+                .with_strict(strict)
+                .with_quiet_errors(quiet_errors);
+            if let Some(rate) = rate_limit {
+                scraper = scraper.with_rate_limit(rate);
+            }
+            if !dry_run && cli.backend == Backend::Json {
+                scraper = scraper.with_checkpoints(&cache_path, DEFAULT_CHECKPOINT_INTERVAL);
+            }
+            let previous = load_database(cli.backend, &cache_path).unwrap_or_default();
+            let reuse_unchanged = !refetch_all && !previous.mods.is_empty();
+            if reuse_unchanged {
+                scraper = scraper.with_previous(Arc::new(previous.clone()));
+            }
+            let since = if since_last_run {
+                let last_run = DateTime::parse_from_rfc3339(&previous.last_updated)
+                    .map_err(|_| anyhow!("The cached database has no valid last-updated time; run a full update"))?;
+                Some(last_run.with_timezone(&Utc))
+            } else {
+                since
+            };
+            
+            // A full crawl stamps page edit times itself; the other updates fetch them afterwards
+            let full_crawl = category.is_none() && since.is_none();
+            // Known only for full crawls: the final mod-page progress and phase timings
+            let mut crawl: Option<UpdateProgress> = None;
+            let mut crawl_timings: Option<UpdateTimings> = None;
+            let (mut db, summary, failures) = if let Some(category) = category {
+                let mut db = previous.clone();
+                let canonical = categories.canonical(&category);
+                let Some(category) = db.resolve_category(&categories, &category)
+                    .or_else(|| categories.wiki_category(canonical).map(|_| canonical))
+                    .map(str::to_string)
+                else {
+                    let hint = db.suggest_category(&categories, &category)
+                        .map(|key| format!(" (did you mean '{}'?)", key))
+                        .unwrap_or_default();
+                    return Err(anyhow!("Category '{}' not found{}", category, hint));
+                };
+                let failures = scraper.update_category(&mut db, &category, verbose).await?;
+                let count = db.categories.get(&category).map(|mods| mods.len()).unwrap_or(0);
+                (db, format!("✅ Category '{}' updated with {} mods", category, count), failures)
+            } else if let Some(since) = since {
+                if previous.mods.is_empty() {
+                    return Err(anyhow!("No cached database to update incrementally; run a full update first"));
+                }
+                let mut db = previous.clone();
+                let (refreshed, failures) = scraper.update_changed_since(&mut db, since, verbose).await?;
+                (db, format!("✅ Refreshed {} mods changed since {}", refreshed, since.to_rfc3339()), failures)
+            } else {
+                let on_progress = |progress: &UpdateProgress| {
+                    crawl = Some(progress.clone());
+                    if let Some(path) = &progress_file {
+                        // Monitoring is best-effort; a failed write shouldn't abort the crawl
+                        if let Ok(json) = serde_json::to_string(progress) {
+                            let _ = std::fs::write(path, json);
+                        }
+                    }
+                };
+                let on_mod = |mod_info: &ModInfo| {
+                    if stream {
+                        if let Ok(json) = serde_json::to_string(mod_info) {
+                            println!("{}", json);
+                        }
+                    }
+                };
+                let result = scraper.update_database_streaming(verbose, on_progress, on_mod).await;
+                if let Some(path) = &progress_file {
+                    let _ = std::fs::remove_file(path);
+                }
+                let (mut db, phase_timings, failures) = result?;
+                crawl_timings = Some(phase_timings);
+                db.carry_over_timestamps(&previous);
+                let mut summary = match &cli.profile {
+                    Some(profile) => format!("✅ Profile '{}' updated with {} mods", profile, db.mods.len()),
+                    None => format!("✅ Database updated with {} mods", db.mods.len()),
+                };
+                if let Some(skipped) = crawl.as_ref().map(|progress| progress.skipped).filter(|&n| n > 0) {
+                    summary.push_str(&format!(" (skipped {} non-mod pages)", skipped));
+                }
+                if let Some(progress) = crawl.as_ref().filter(|_| reuse_unchanged) {
+                    summary.push_str(&format!(
+                        " ({} unchanged pages reused, {} refetched)",
+                        progress.unchanged,
+                        progress.completed - progress.unchanged
+                    ));
+                }
+                (db, summary, failures)
+            };
+            if !full_crawl {
+                match scraper.fill_last_edited(&mut db).await {
+                    Ok(fetched) if verbose => println!("✏️  Fetched page edit times for {} mods", fetched),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("⚠️  Could not fetch page edit times: {}", e),
+                }
+            }
+            if github_stars {
+                let fetched = scraper.fill_github_stars(&mut db).await;
+                if verbose {
+                    println!("⭐ Fetched GitHub stars for {} mods", fetched);
+                }
+            }
+            
+            let timings_report = crawl_timings.as_ref().filter(|_| timings).map(format_timings);
+            if dry_run {
+                print_dry_run_report(&db, crawl.as_ref());
+                println!("📡 HTTP requests: {}", scraper.request_count());
+                if let Some(report) = &timings_report {
+                    println!("\n{}", report);
+                }
+                return failures_to_result(&failures);
+            }
+            
+            ChangeSet::between(&previous, &db).save(&ChangeSet::path_for(&cache_path))?;
+            save_database(&previous, &db, cli.backend, &cache_path)?;
+            ModDatabase::discard_partial(&cache_path);
+            if full_crawl {
+                http_cache.prune_unused();
+            }
+            http_cache.save(&HttpCache::path_for(&cache_path))?;
+            let summary = format!("{}, using {} HTTP requests", summary, scraper.request_count());
+            if stream {
+                eprintln!("{}", summary);
+            } else {
+                println!("{}", summary);
+            }
+            if let Some(report) = &timings_report {
+                if stream {
+                    eprintln!("\n{}", report);
+                } else {
+                    println!("\n{}", report);
+                }
+            }
+            if let Some(path) = &generate_sitemap {
+                write_sitemap(&db, path)?;
+            }
+            if verify_wikipedia_links {
+                verify_wikilinks(&db, &scraper).await;
+            }
+            failures_to_result(&failures)?;
+        }
+        Commands::Doctor => {
+            let scraper = WikiScraper::from_config(scraper_config.clone());
+            run_doctor(&cache_path, &scraper, cli.max_age, cli.offline).await?;
+        }
+        Commands::Snapshot { output } => {
+            if !cache_path.exists() {
+                return Err(anyhow!("No cached database to snapshot; run `balatro-wiki update` first"));
+            }
+            let snapshot = Snapshot::capture(&cache_path)?;
+            snapshot.write(&output)?;
+            println!("📦 Wrote snapshot of {} mods to {}", snapshot.database.mods.len(), output.display());
+        }
+        Commands::Restore { input } => {
+            let snapshot = Snapshot::read(&input)?;
+            if let Some(backup) = snapshot.restore(&cache_path)? {
+                println!("💾 Backed up the previous cache to {}", backup.display());
+            }
+            println!("✅ Restored {} mods from snapshot taken {}", snapshot.database.mods.len(), snapshot.created_at);
+        }
+        _ => {
+            let mut db = if let Some(source) = &cli.db {
+                if !matches!(cli.command, Commands::Browse { .. } | Commands::Search { .. } | Commands::Info { .. }) {
+                    return Err(anyhow!("--db is only supported by browse, search, and info"));
+                }
+                read_database(source)?
+            } else {
+                let partial = match cli.backend {
+                    Backend::Json => None,
+                    #[cfg(feature = "sqlite")]
+                    Backend::Sqlite => load_sqlite_subset(&cli.command, &cache_path, cli.max_age, cli.offline)?,
+                    #[cfg(not(feature = "sqlite"))]
+                    Backend::Sqlite => None,
+                };
+                match partial {
+                    Some(db) => db,
+                    None => {
+                        let scraper = WikiScraper::from_config(scraper_config.clone());
+                        load_fresh_database(cli.backend, &scraper, &cache_path, cli.max_age, cli.offline, cli.verbose)
+                            .await?
+                    }
+                }
+            };
+            
+            match cli.command {
+                Commands::Browse {
+                    category,
+                    output,
+                    tsv_columns,
+                    fields,
+                    exclude_fields,
+                    not_bookmarked,
+                    bookmarked_only,
+                    only_mods_with_releases,
+                    only_with_github,
+                    filter_json,
+                    sort,
+                    after_alphabetical,
+                    reverse,
+                    compact,
+                    no_header,
+                    desc_len,
+                    name_only,
+                } => {
+                    if only_mods_with_releases && !cli.offline && cli.db.is_none() {
+                        let scraper = WikiScraper::from_config(scraper_config.clone());
+                        let previous = db.clone();
+                        if scraper.fill_github_releases(&mut db).await > 0 {
+                            save_database(&previous, &db, cli.backend, &cache_path)?;
+                        }
+                    }
+                    let bookmark_filter = if not_bookmarked || bookmarked_only {
+                        let bookmarks = Bookmarks::load(&Bookmarks::favorites_path_for(&cache_path))?;
+                        Some(if bookmarked_only {
+                            BookmarkFilter::Only(bookmarks)
+                        } else {
+                            BookmarkFilter::Exclude(bookmarks)
+                        })
+                    } else {
+                        None
+                    };
+                    let opts = BrowseOpts {
+                        output: if name_only {
+                            OutputFormat::Names
+                        } else if cli.json {
+                            OutputFormat::Json
+                        } else {
+                            output
+                        },
+                        json_fields: cli.json_fields.clone(),
+                        tsv_columns,
+                        fields: FieldSet::from_args(&fields, &exclude_fields, BROWSE_CARD_FIELDS),
+                        bookmark_filter,
+                        only_with_releases: only_mods_with_releases,
+                        only_with_github,
+                        json_filter: filter_json.as_deref().map(JsonFilter::parse).transpose()?,
+                        sort,
+                        reverse,
+                        after_letter: after_alphabetical,
+                        compact,
+                        no_header,
+                        desc_len,
+                    };
+                    browse_mods(&db, &categories, category, &opts).await?;
+                }
+                Commands::Search {
+                    query,
+                    regex,
+                    ignore_case,
+                    semantic,
+                    category,
+                    author,
+                    tag,
+                    only_with_github,
+                    fields_to_search,
+                    all,
+                    sort,
+                    reverse,
+                    min_score,
+                    output,
+                    tsv_columns,
+                    highlight_fields,
+                    highlight,
+                    desc_len,
+                    name_only,
+                } => {
+                    let opts = SearchOpts {
+                        regex,
+                        ignore_case,
+                        category: category.map(|category| categories.canonical(&category).to_string()),
+                        author,
+                        tag,
+                        fields: search_fields(fields_to_search),
+                        all_words: all,
+                        min_score,
+                        ..Default::default()
+                    };
+                    let view = SearchView {
+                        output: if name_only {
+                            OutputFormat::Names
+                        } else if cli.json {
+                            OutputFormat::Json
+                        } else {
+                            output
+                        },
+                        json_fields: cli.json_fields.clone(),
+                        tsv_columns,
+                        highlight_fields,
+                        snippets: highlight,
+                        only_with_github,
+                        sort,
+                        reverse,
+                        verbose: cli.verbose,
+                        desc_len,
+                    };
+                    let found = if semantic {
+                        let index = load_or_build_index(&db, &TfIdfIndex::path_for(&cache_path))?;
+                        semantic_search_mods(&db, &index, &query, &opts, &view)?
+                    } else {
+                        search_mods(&db, &query, opts, &view)?
+                    };
+                    if found == 0 {
+                        return Err(CliError::NoMatches.into());
+                    }
+                }
+                Commands::Index => {
+                    let index = TfIdfIndex::build(&db);
+                    let path = TfIdfIndex::path_for(&cache_path);
+                    index.save(&path)?;
+                    println!("✅ Indexed {} mods to {}", db.mods.len(), path.display());
+                }
+                Commands::Info { name, diff_against_live, update, open_in_browser, github, open, format } => {
+                    if diff_against_live {
+                        if cli.offline {
+                            return Err(anyhow!("Cannot compare against the live wiki in offline mode"));
+                        }
+                        if cli.db.is_some() {
+                            return Err(anyhow!("Cannot compare against the live wiki when reading --db"));
+                        }
+                        if update && cli.backend != Backend::Json {
+                            return Err(anyhow!("info --update only supports --backend json"));
+                        }
+                        diff_mod_against_live(&mut db, &cache_path, &WikiScraper::from_config(scraper_config.clone()), &name, update).await?;
+                    } else if let Some(template) = format {
+                        let mod_info = db.find_normalized(&name)
+                            .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+                        println!("{}", format_mod(&template, mod_info));
+                    } else if cli.json {
+                        let mod_info = db.find_normalized(&name)
+                            .ok_or_else(|| CliError::NotFound(name.to_string()))?;
+                        println!("{}", serde_json::to_string_pretty(&mod_json(mod_info, &cli.json_fields)?)?);
+                    } else {
+                        show_mod_info(&db, &name)?;
+                    }
+                    let open = if open_in_browser {
+                        let (target, value) = match github {
+                            true => (OpenTarget::Github, "github"),
+                            false => (OpenTarget::Wiki, "wiki"),
+                        };
+                        eprintln!("⚠️  --open-in-browser and --github are deprecated; use --open {}", value);
+                        // Unlike --open github, the old flags fall back to the wiki page
+                        match db.find_normalized(&name) {
+                            Some(mod_info) if github && mod_info.github_url.is_none() => {
+                                eprintln!("⚠️  {} has no GitHub link; opening the wiki page instead", mod_info.name);
+                                Some(OpenTarget::Wiki)
+                            }
+                            _ => Some(target),
+                        }
+                    } else {
+                        open
+                    };
+                    if let Some(target) = open {
+                        open_mod_page(&db, &name, target)?;
+                    }
+                }
+                Commands::Analyze { name } => {
+                    analyze_mod(&db, &name)?;
+                }
+                Commands::Compare { names } => {
+                    compare_mods(&db, &names);
+                }
+                Commands::Export { output, output_dir, incremental } => {
+                    match output_dir {
+                        Some(dir) => export_dossiers(&db, &dir, incremental)?,
+                        None => export_mods(&db, output.as_deref(), incremental, &cli.json_fields)?,
+                    }
                 }
                 Commands::Categories => {
-                    list_categories(&db);
+                    list_categories(&db, &categories);
+                }
+                Commands::Count { category } => {
+                    let count = match category {
+                        Some(category) => count_in_category(&db, &categories, &category)?,
+                        None => db.mods.len(),
+                    };
+                    if cli.json {
+                        println!("{}", serde_json::json!({ "count": count }));
+                    } else {
+                        println!("{}", count);
+                    }
+                }
+                Commands::Changed { since } => {
+                    show_changes(&ChangeSet::path_for(&cache_path), since)?;
+                }
+                Commands::Recent { limit, since } => {
+                    let scraper = (!cli.offline).then(|| WikiScraper::from_config(scraper_config.clone()));
+                    let limit = limit.unwrap_or(if since.is_some() { usize::MAX } else { 10 });
+                    show_recent_mods(&db, &cache_path, scraper.as_ref(), limit, since, cli.verbose).await?;
+                }
+                Commands::Fav { name } => {
+                    add_favorite(&db, &Bookmarks::favorites_path_for(&cache_path), &name)?;
                 }
-                Commands::Update => unreachable!(),
+                Commands::Unfav { name } => {
+                    remove_favorite(&Bookmarks::favorites_path_for(&cache_path), &name)?;
+                }
+                Commands::Favs => {
+                    list_favorites(&db, &Bookmarks::favorites_path_for(&cache_path))?;
+                }
+                Commands::CheckLinks { concurrency } => {
+                    if cli.offline {
+                        return Err(anyhow!("Cannot check links in offline mode"));
+                    }
+                    let scraper = WikiScraper::from_config(scraper_config.clone());
+                    check_links(&db, &scraper, concurrency as usize, cli.json).await?;
+                }
+                Commands::Watch { category, interval, interval_secs } => {
+                    if cli.offline {
+                        return Err(anyhow!("Cannot watch the wiki in offline mode"));
+                    }
+                    let http_cache = Arc::new(HttpCache::load(&HttpCache::path_for(&cache_path)));
+                    let scraper = WikiScraper::from_config(scraper_config.clone()).with_http_cache(http_cache.clone());
+                    let interval = Duration::from_secs(interval_secs.unwrap_or(interval * 60));
+                    match category {
+                        Some(category) => {
+                            let category = categories.canonical(&category);
+                            watch_category(&mut db, &cache_path, &scraper, &http_cache, &categories, category, interval)
+                                .await?
+                        }
+                        None => watch_wiki(db, &cache_path, &scraper, &http_cache, interval, cli.verbose).await?,
+                    }
+                }
+                Commands::Install { name, record_only } => {
+                    install_mod(&db, &InstalledMods::path_for(&cache_path), &name, record_only)?;
+                }
+                Commands::Installed => {
+                    list_installed(&db, &InstalledMods::path_for(&cache_path))?;
+                }
+                Commands::Uninstall { name, remove_files } => {
+                    uninstall_mod(&InstalledMods::path_for(&cache_path), &name, remove_files)?;
+                }
+                Commands::Update { .. }
+                | Commands::Doctor
+                | Commands::Snapshot { .. }
+                | Commands::Restore { .. } => unreachable!(),
             }
         }
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn since_accepts_each_unit_and_rejects_others() {
+        let now = Utc::now();
+        for (input, expected) in [
+            ("30m", chrono::Duration::minutes(30)),
+            ("48h", chrono::Duration::hours(48)),
+            ("7d", chrono::Duration::days(7)),
+            ("2w", chrono::Duration::weeks(2)),
+        ] {
+            let ago = now - parse_since(input).unwrap();
+            assert!((ago - expected).num_seconds().abs() < 5, "{} parsed as {:?} ago", input, ago);
+        }
+        for input in ["", "7", "d", "7 days", "-3d", "1.5h"] {
+            assert!(parse_since(input).unwrap_err().contains("30m, 48h, 7d, 2w"), "{} should be rejected", input);
+        }
+        assert_eq!(parse_duration("0"), Ok(chrono::Duration::zero()));
+    }
+
+    #[test]
+    fn search_in_scores_only_the_chosen_field() {
+        let mut cryptid = ModInfo::named("Cryptid");
+        cryptid.description = "Adds new Joker cards".to_string();
+        let joker_pack = ModInfo::named("Joker Pack");
+        let score = |mod_info: &ModInfo, args: &[&str]| {
+            let cli = Cli::try_parse_from(["balatro-wiki", "search", "joker"].iter().chain(args)).unwrap();
+            let Commands::Search { fields_to_search, .. } = cli.command else {
+                unreachable!("parsed a search command");
+            };
+            let fields = search_fields(fields_to_search);
+            let fields = if fields.is_empty() { SearchField::ALL.to_vec() } else { fields };
+            balatro_wiki::calculate_search_score_in(mod_info, "joker", &fields)
+        };
+        
+        assert!(score(&cryptid, &["--in", "description"]) > 0);
+        assert_eq!(score(&cryptid, &["--in", "name"]), 0);
+        assert_eq!(score(&joker_pack, &["--in", "description,author"]), 0);
+        assert_eq!(score(&joker_pack, &["--fields-to-search", "author"]), 0);
+        assert_eq!(score(&joker_pack, &["--in", "all"]), score(&joker_pack, &["--in", "name"]));
+        assert_eq!(score(&joker_pack, &[]), score(&joker_pack, &["--in", "name,all"]));
+    }
+
+    #[test]
+    fn search_sorts_every_match_before_keeping_the_top_ones() {
+        let mods: Vec<ModInfo> = (0..30u32)
+            .map(|i| ModInfo { stars: Some(i), ..ModInfo::named(&format!("Mod {}", i)) })
+            .collect();
+        let mut listed: Vec<&ModInfo> = mods.iter().collect();
+        
+        top_results(&mut listed, Some(SortKey::Stars), true, |mod_info| mod_info);
+        assert_eq!(listed.len(), 20);
+        assert_eq!(listed.first().unwrap().stars, Some(10));
+        assert_eq!(listed.last().unwrap().stars, Some(29));
+    }
+
+    #[test]
+    fn reversed_name_sort_is_reverse_alphabetical() {
+        let mods: Vec<ModInfo> = ["Cryptid", "Aura", "Talisman", "Bunco"].into_iter().map(ModInfo::named).collect();
+        let mut listed: Vec<&ModInfo> = mods.iter().collect();
+        
+        arrange(&mut listed, Some(SortKey::Name), true, |mod_info| mod_info);
+        
+        let names: Vec<&str> = listed.iter().map(|mod_info| mod_info.name.as_str()).collect();
+        assert_eq!(names, ["Talisman", "Cryptid", "Bunco", "Aura"]);
+    }
+
+    #[test]
+    fn age_sort_puts_recent_edits_first_and_unknown_last() {
+        let mut mods: Vec<ModInfo> = ["Cryptid", "Aura", "Talisman"].into_iter().map(ModInfo::named).collect();
+        mods[0].last_edited = Some("2024-01-05T10:00:00Z".to_string());
+        mods[2].last_edited = Some("2024-03-01T08:00:00Z".to_string());
+        let mut listed: Vec<&ModInfo> = mods.iter().collect();
+        
+        arrange(&mut listed, Some(SortKey::Age), false, |mod_info| mod_info);
+        
+        let names: Vec<&str> = listed.iter().map(|mod_info| mod_info.name.as_str()).collect();
+        assert_eq!(names, ["Talisman", "Cryptid", "Aura"]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// A token bucket that limits how many requests per second may start,
+/// however many tasks are sharing it.
+#[derive(Debug)]
+pub struct TokenBucket {
+    requests_per_second: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a bucket that allows `requests_per_second` requests per
+    /// second, with no bursting beyond a single request.
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second,
+            state: Mutex::new(BucketState { tokens: 1.0, last_refill: Instant::now() }),
+        }
+    }
+
+    /// Creates a bucket that lets one request start every `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self::new(1.0 / interval.as_secs_f64())
+    }
+
+    /// Waits until a request may be made, then consumes a token.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.requests_per_second).min(1.0);
+                state.last_refill = now;
+                
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.requests_per_second)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::database::ModInfo;
+
+/// How long a fetched list of recent mods is reused before the wiki is asked again.
+pub const RECENT_CACHE_TTL: Duration = Duration::minutes(15);
+
+/// The most recently created or edited mod pages, newest first, as fetched
+/// by the `recent` command. Stored as `recent.json` next to the mod cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentMods {
+    /// When the list was fetched (RFC 3339).
+    pub fetched_at: String,
+    /// How many mods were asked for; a shorter list means the wiki had no more.
+    pub limit: usize,
+    /// The oldest edit time asked for (RFC 3339), if the list was cut off by age.
+    #[serde(default)]
+    pub since: Option<String>,
+    pub mods: Vec<ModInfo>,
+}
+
+impl RecentMods {
+    /// Wraps a freshly fetched list.
+    pub fn new(mods: Vec<ModInfo>, limit: usize, since: Option<DateTime<Utc>>) -> Self {
+        Self { fetched_at: Utc::now().to_rfc3339(), limit, since: since.map(|since| since.to_rfc3339()), mods }
+    }
+
+    /// Returns the cache file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("recent.json")
+    }
+
+    /// Loads the cached list, or `None` if it doesn't exist or can't be read.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Writes the list to `path`, replacing the previous one.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns true if the list is younger than [`RECENT_CACHE_TTL`] and
+    /// covers a request for `limit` mods edited after `since`: it was fetched
+    /// with at least that many entries and reaches back at least as far.
+    pub fn is_fresh_for(&self, limit: usize, since: Option<DateTime<Utc>>) -> bool {
+        let Ok(fetched) = DateTime::parse_from_rfc3339(&self.fetched_at) else {
+            return false;
+        };
+        let reaches_back = match (since, &self.since) {
+            (None, None) => true,
+            (Some(wanted), Some(cached)) => DateTime::parse_from_rfc3339(cached).is_ok_and(|cached| cached <= wanted),
+            _ => false,
+        };
+        self.limit >= limit && reaches_back && Utc::now() - fetched.with_timezone(&Utc) < RECENT_CACHE_TTL
+    }
+
+    /// The cached mods last edited at or after `since`, newest first.
+    pub fn edited_since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &ModInfo> {
+        self.mods.iter().filter(move |mod_info| {
+            mod_info.last_edited.as_deref()
+                .and_then(|edited| DateTime::parse_from_rfc3339(edited).ok())
+                .is_some_and(|edited| edited >= since)
+        })
+    }
+}
@@ -0,0 +1,1701 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use scraper::Html;
+use serde::Serialize;
+use std::cell::Cell;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::categories::Categories;
+use crate::database::{ModDatabase, ModInfo};
+use crate::github::{self, GITHUB_API_URL};
+use crate::http_cache::{CachedResponse, HttpCache};
+use crate::rate_limit::TokenBucket;
+use crate::selectors::LazySelector;
+use crate::text::{extract_description, extract_features};
+use crate::WIKI_BASE_URL;
+
+/// How long a single request may take before it is abandoned.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Minimum spacing between requests unless configured otherwise, so a full
+/// update doesn't burst the wiki.
+pub const DEFAULT_REQUEST_DELAY: Duration = Duration::from_millis(100);
+
+/// Category members requested per API call unless configured otherwise.
+pub const DEFAULT_CATEGORY_PAGE_SIZE: u32 = 50;
+
+/// The most category members MediaWiki returns per call to anonymous clients.
+pub const MAX_CATEGORY_PAGE_SIZE: u32 = 500;
+
+/// Completed mod pages between the saves made by [`WikiScraper::with_checkpoints`].
+pub const DEFAULT_CHECKPOINT_INTERVAL: usize = 50;
+
+/// How far back the wiki's recent-changes feed goes (MediaWiki's default
+/// `$wgRCMaxAge`). Changes older than this can't be listed.
+pub const RECENT_CHANGES_MAX_AGE: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+/// Pages of 50 recent changes [`WikiScraper::recent_mods`] reads at most
+/// while looking for mod pages.
+pub const RECENT_MODS_MAX_PAGES: usize = 20;
+
+/// Identifies the tool to wiki admins instead of posing as a browser.
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "balatro-wiki/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/wogyfikacja/flake-balatro)"
+);
+
+tokio::task_local! {
+    /// Time the current mod-page task has spent waiting for the rate
+    /// limiter, so per-mod timings only count the requests themselves.
+    static THROTTLED: Cell<Duration>;
+}
+
+/// Settings fixed when a [`WikiScraper`]'s HTTP client is built.
+#[derive(Debug, Clone)]
+pub struct ScraperConfig {
+    pub timeout: Duration,
+    pub user_agent: String,
+    /// Wiki root that `/wiki/<page>` and `/w/api.php` are resolved against.
+    pub base_url: String,
+    /// Minimum time between the start of two requests, shared by every
+    /// clone of the scraper. Zero disables the limit.
+    pub request_delay: Duration,
+    /// Category members fetched per API call, capped at
+    /// [`MAX_CATEGORY_PAGE_SIZE`]. Listings still follow continuation, so
+    /// this trades request count against response size, not completeness.
+    pub category_page_size: u32,
+    /// Categories a full update crawls.
+    pub categories: Categories,
+    /// GitHub REST API root that `/repos/...` lookups are resolved against.
+    pub github_api_url: String,
+}
+
+impl Default for ScraperConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            base_url: WIKI_BASE_URL.to_string(),
+            request_delay: DEFAULT_REQUEST_DELAY,
+            category_page_size: DEFAULT_CATEGORY_PAGE_SIZE,
+            categories: Categories::default(),
+            github_api_url: GITHUB_API_URL.to_string(),
+        }
+    }
+}
+
+/// HTTP client for the Balatro mods wiki.
+#[derive(Clone)]
+pub struct WikiScraper {
+    client: Client,
+    base_url: String,
+    rate_limiter: Option<Arc<TokenBucket>>,
+    category_page_size: u32,
+    http_cache: Option<Arc<HttpCache>>,
+    categories: Arc<Categories>,
+    checkpoint: Option<(Arc<Path>, usize)>,
+    previous: Option<Arc<ModDatabase>>,
+    strict: bool,
+    quiet_errors: bool,
+    requests: Arc<AtomicU64>,
+    github_api_url: String,
+    github_rate_limited: Arc<AtomicBool>,
+}
+
+impl Default for WikiScraper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WikiScraper {
+    /// Creates a scraper with the default user agent, a 30 second timeout,
+    /// and 100ms between requests.
+    pub fn new() -> Self {
+        Self::from_config(ScraperConfig::default())
+    }
+
+    /// Creates a scraper whose requests give up after `timeout`.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::from_config(ScraperConfig { timeout, ..ScraperConfig::default() })
+    }
+
+    /// Creates a scraper for the wiki rooted at `base_url`, e.g. a mirror or test server.
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self::from_config(ScraperConfig { base_url: base_url.to_string(), ..ScraperConfig::default() })
+    }
+
+    /// Creates a scraper from explicit client settings. Panics if
+    /// `config.user_agent` isn't a valid header value, e.g. has a newline.
+    pub fn from_config(config: ScraperConfig) -> Self {
+        let client = Client::builder()
+            .user_agent(config.user_agent)
+            .timeout(config.timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+            
+        let rate_limiter = (!config.request_delay.is_zero())
+            .then(|| Arc::new(TokenBucket::with_interval(config.request_delay)));
+        Self {
+            client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            rate_limiter,
+            category_page_size: config.category_page_size,
+            http_cache: None,
+            categories: Arc::new(config.categories),
+            checkpoint: None,
+            previous: None,
+            strict: false,
+            quiet_errors: false,
+            requests: Arc::new(AtomicU64::new(0)),
+            github_api_url: config.github_api_url.trim_end_matches('/').to_string(),
+            github_rate_limited: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Limits the scraper (and every clone of it) to `requests_per_second`
+    /// requests in total, regardless of how many run concurrently. Replaces
+    /// the configured `request_delay`.
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limiter = Some(Arc::new(TokenBucket::new(requests_per_second)));
+        self
+    }
+
+    /// Revalidates category listings and mod pages against `cache`, shared
+    /// by every clone of the scraper. Save it after the crawl to reuse the
+    /// bodies next time.
+    pub fn with_http_cache(mut self, cache: Arc<HttpCache>) -> Self {
+        self.http_cache = Some(cache);
+        self
+    }
+
+    /// Saves the partly built database after every `every` completed mod
+    /// pages of a full update, to the checkpoint file beside `cache_path`
+    /// (see [`ModDatabase::partial_path_for`]). The cache itself is left
+    /// alone until the caller saves the finished crawl, so an interrupted
+    /// update never replaces a complete cache with a partial one.
+    pub fn with_checkpoints(mut self, cache_path: &Path, every: usize) -> Self {
+        self.checkpoint = Some((Arc::from(ModDatabase::partial_path_for(cache_path)), every.max(1)));
+        self
+    }
+
+    /// During a full update, reuses `previous`'s copy of each mod whose wiki
+    /// page hasn't been edited since its cached `last_edited`, instead of
+    /// scraping the page again. Reused mods keep their cached GitHub
+    /// Releases check.
+    pub fn with_previous(mut self, previous: Arc<ModDatabase>) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    /// Makes updates fail at the first category listing or mod page that
+    /// can't be scraped, instead of recording it in [`UpdateFailures`] and
+    /// carrying on.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Stops updates from logging each category listing or mod page that
+    /// can't be scraped; they are still recorded in [`UpdateFailures`].
+    pub fn with_quiet_errors(mut self, quiet_errors: bool) -> Self {
+        self.quiet_errors = quiet_errors;
+        self
+    }
+
+    /// Handles a mod page that couldn't be scraped during an update. Pages
+    /// that aren't mods are only logged; anything else is recorded in
+    /// `failures`, or fails the update in strict mode. Returns the line to
+    /// log, if any.
+    fn mod_failure(
+        &self,
+        failures: &mut UpdateFailures,
+        mod_name: &str,
+        error: anyhow::Error,
+    ) -> Result<Option<String>> {
+        if !error.is::<NotAModPage>() {
+            if self.strict {
+                return Err(error.context(format!("Failed to scrape {}", mod_name)));
+            }
+            failures.mods.push(mod_name.to_string());
+            if self.quiet_errors {
+                return Ok(None);
+            }
+        }
+        Ok(Some(scrape_failure_line(mod_name, &error)))
+    }
+
+    /// Handles a scrape task that panicked during an update, like
+    /// [`WikiScraper::mod_failure`].
+    fn task_failure(&self, failures: &mut UpdateFailures, error: tokio::task::JoinError) -> Result<Option<String>> {
+        if self.strict {
+            return Err(error.into());
+        }
+        failures.tasks += 1;
+        Ok((!self.quiet_errors).then(|| format!("  ✗ Task failed: {}", error)))
+    }
+
+    /// Number of HTTP requests this scraper and its clones have sent.
+    pub fn request_count(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Waits for the rate limiter, if any, and counts the request about to be sent.
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.rate_limiter {
+            let waiting = Instant::now();
+            limiter.acquire().await;
+            let _ = THROTTLED.try_with(|throttled| throttled.set(throttled.get() + waiting.elapsed()));
+        }
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sends `request` and returns the response body. With an HTTP cache,
+    /// the request carries the validators from the last fetch of the same
+    /// URL, and a `304 Not Modified` answer returns the cached body.
+    async fn fetch_text(&self, request: RequestBuilder) -> Result<String> {
+        Ok(self.fetch(request).await?.1)
+    }
+
+    /// Like [`WikiScraper::fetch_text`], also returning the response status.
+    /// A cached body revalidated by a `304 Not Modified` comes back as `200 OK`.
+    async fn fetch(&self, request: RequestBuilder) -> Result<(StatusCode, String)> {
+        let mut request = request.build()?;
+        let url = request.url().to_string();
+        let cached = self.http_cache.as_ref().and_then(|cache| cache.get(&url));
+        if let Some(cached) = &cached {
+            let headers = request.headers_mut();
+            if let Some(etag) = cached.etag.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = cached.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(IF_MODIFIED_SINCE, modified);
+            }
+        }
+        
+        self.throttle().await;
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok((StatusCode::OK, cached.body));
+            }
+        }
+        
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let cacheable = status.is_success() && (etag.is_some() || last_modified.is_some());
+        let body = response.text().await?;
+        if let Some(cache) = self.http_cache.as_ref().filter(|_| cacheable) {
+            cache.insert(&url, CachedResponse { etag, last_modified, body: body.clone() });
+        }
+        Ok((status, body))
+    }
+
+    /// Fetches `path` (e.g. `/repos/owner/name`) from the GitHub API as JSON,
+    /// sending `GITHUB_TOKEN` when it is set. Goes through the rate limiter
+    /// and HTTP cache like wiki requests. Once GitHub refuses a request for
+    /// exceeding its rate limit, later calls from this scraper and its
+    /// clones fail without sending anything.
+    async fn github_json(&self, path: &str) -> Result<serde_json::Value> {
+        if self.github_rate_limited.load(Ordering::Relaxed) {
+            return Err(anyhow!("GitHub API rate limit reached; set GITHUB_TOKEN to raise it"));
+        }
+        let mut request = self.client
+            .get(format!("{}{}", self.github_api_url, path))
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = github::token() {
+            request = request.bearer_auth(token);
+        }
+        
+        let (status, body) = self.fetch(request).await?;
+        if status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS {
+            self.github_rate_limited.store(true, Ordering::Relaxed);
+            return Err(anyhow!("GitHub API rate limit reached; set GITHUB_TOKEN to raise it"));
+        }
+        if !status.is_success() {
+            return Err(anyhow!("GitHub API returned {} for {}", status, path));
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Lists the page titles in a wiki category via the MediaWiki API,
+    /// skipping category, file, and template pages. Asks for `page_size`
+    /// members per request (capped at [`MAX_CATEGORY_PAGE_SIZE`]) and follows
+    /// the API's continuation tokens so every member is listed.
+    pub async fn scrape_category_page_with_verbosity(&self, category: &str, page_size: u32, verbose: bool) -> Result<Vec<String>> {
+        let page_size = page_size.clamp(1, MAX_CATEGORY_PAGE_SIZE);
+        let mut mod_names = Vec::new();
+        let mut cmcontinue: Option<String> = None;
+        
+        loop {
+            // Use MediaWiki API instead of HTML scraping
+            let api_url = format!("{}/w/api.php?action=query&list=categorymembers&cmtitle=Category:{}&format=json&cmlimit={}",
+                                  self.base_url, category.replace(' ', "%20"), page_size);
+            if verbose {
+                match &cmcontinue {
+                    Some(token) => println!("  API request: {} (continuing from {})", api_url, token),
+                    None => println!("  API request: {}", api_url),
+                }
+            }
+            
+            let mut request = self.client
+                .get(&api_url)
+                .header("Accept", "application/json");
+            if let Some(token) = &cmcontinue {
+                request = request.query(&[("cmcontinue", token)]);
+            }
+            
+            let json_text = self.fetch_text(request).await?;
+            if verbose {
+                println!("  Got {} bytes of JSON", json_text.len());
+            }
+            
+            // Parse JSON response
+            let json: serde_json::Value = serde_json::from_str(&json_text)?;
+            
+            if let Some(members) = json.pointer("/query/categorymembers").and_then(|m| m.as_array()) {
+                if verbose {
+                    println!("  Found {} category members", members.len());
+                }
+                
+                for member in members {
+                    if let Some(title) = member.get("title").and_then(|t| t.as_str()) {
+                        // Skip category pages and other namespace pages
+                        if !is_non_mod_namespace(title) {
+                            mod_names.push(title.to_string());
+                            if verbose {
+                                println!("    ✓ {}", title);
+                            }
+                        }
+                    }
+                }
+            }
+            
+            cmcontinue = json.pointer("/continue/cmcontinue")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_string());
+            match &cmcontinue {
+                Some(_) if verbose => println!("  {} mod names so far, fetching next page...", mod_names.len()),
+                Some(_) => {}
+                None => break,
+            }
+        }
+        
+        if verbose {
+            println!("  Extracted {} mod names", mod_names.len());
+        }
+        Ok(mod_names)
+    }
+
+    /// Lists every page title in a wiki category without logging, using the
+    /// configured page size.
+    pub async fn scrape_category_page_all(&self, category: &str) -> Result<Vec<String>> {
+        self.scrape_category_page_with_verbosity(category, self.category_page_size, false).await
+    }
+
+    /// Fetches and parses a single mod page. The returned categories are
+    /// empty; callers fill them in from the category listings. Fails with
+    /// [`NotAModPage`] for disambiguation pages and stubs.
+    pub async fn scrape_mod_page(&self, mod_name: &str) -> Result<ModInfo> {
+        let mut url = format!("{}/wiki/{}", self.base_url, mod_name);
+        let mut html = self.fetch_text(self.client.get(&url)).await?;
+        
+        // A redirect page that was served as-is: hop once to its target
+        if let Some(target) = redirect_target(&html, &self.base_url)? {
+            url = target;
+            html = self.fetch_text(self.client.get(&url)).await?;
+        }
+        
+        let document = Html::parse_document(&html);
+        
+        // MediaWiki followed a redirect for us: store the canonical page URL
+        if let Some(canonical) = redirected_canonical_url(&document, &self.base_url)? {
+            url = canonical;
+        }
+        
+        if is_non_mod_page(&document)? {
+            return Err(NotAModPage(mod_name.to_string()).into());
+        }
+        
+        // Extract basic info
+        let name = document
+            .select(TITLE.get()?)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_else(|| mod_name.to_string());
+
+        // Extract description from multiple sources
+        let description = extract_description(&document)?;
+        let features = extract_features(&document)?;
+
+        // Look for GitHub links, preferring one into a repository
+        let github_url = github::best_repo_url(
+            document.select(GITHUB_LINKS.get()?).filter_map(|el| el.value().attr("href")),
+        );
+
+        let download_urls = download_urls(&document, &self.base_url)?;
+        let image_url = infobox_image_url(&document, &self.base_url)?;
+        let kudos_count = kudos_count(&document)?;
+        let tags = infobox_tags(&document)?;
+
+        let author = infobox_value(&document, "author")?;
+        let version = infobox_value(&document, "version")?;
+
+        Ok(ModInfo {
+            name,
+            description: description.trim().to_string(),
+            author,
+            version,
+            github_url,
+            download_urls,
+            wiki_url: url,
+            image_url,
+            categories: Vec::new(), // Will be set by caller
+            tags,
+            dependencies: Vec::new(),
+            features,
+            last_updated: Some(Utc::now().to_rfc3339()),
+            last_edited: None, // Will be set by caller
+            has_github_releases: None, // Will be set by caller
+            releases_checked: None,
+            kudos_count,
+            stars: None,
+        })
+    }
+
+    /// Lists the main-namespace pages edited or created after `since`, via
+    /// the MediaWiki recent-changes feed. Fails if `since` is further back
+    /// than the feed goes ([`RECENT_CHANGES_MAX_AGE`]), since pages edited
+    /// before then would be silently missed.
+    pub async fn changed_pages_since(&self, since: DateTime<Utc>) -> Result<Vec<String>> {
+        let oldest = Utc::now() - chrono::Duration::from_std(RECENT_CHANGES_MAX_AGE)?;
+        if since < oldest {
+            return Err(anyhow!(
+                "The wiki only keeps {} days of recent changes, so changes since {} can't be listed; run a full update",
+                RECENT_CHANGES_MAX_AGE.as_secs() / (24 * 60 * 60),
+                since.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        let api_url = format!("{}/w/api.php", self.base_url);
+        let rcend = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut titles: Vec<String> = Vec::new();
+        let mut rccontinue: Option<String> = None;
+        
+        loop {
+            let mut request = self.client
+                .get(&api_url)
+                .query(&[
+                    ("action", "query"),
+                    ("list", "recentchanges"),
+                    ("rcnamespace", "0"),
+                    ("rcprop", "title"),
+                    ("rclimit", "500"),
+                    ("rcend", rcend.as_str()),
+                    ("format", "json"),
+                ])
+                .header("Accept", "application/json");
+            if let Some(token) = &rccontinue {
+                request = request.query(&[("rccontinue", token)]);
+            }
+            
+            self.throttle().await;
+            let json: serde_json::Value = request.send().await?.json().await?;
+            let changes = json.pointer("/query/recentchanges").and_then(|c| c.as_array());
+            for change in changes.into_iter().flatten() {
+                if let Some(title) = change.get("title").and_then(|t| t.as_str()) {
+                    if !titles.iter().any(|t| t == title) {
+                        titles.push(title.to_string());
+                    }
+                }
+            }
+            
+            rccontinue = json.pointer("/continue/rccontinue")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_string());
+            if rccontinue.is_none() {
+                break;
+            }
+        }
+        
+        Ok(titles)
+    }
+
+    /// Returns the names of the categories each page in `titles` is listed
+    /// under, keyed by the title as given, asking about 50 pages per API
+    /// request.
+    pub async fn page_categories(&self, titles: &[String]) -> Result<std::collections::HashMap<String, Vec<String>>> {
+        let api_url = format!("{}/w/api.php", self.base_url);
+        let mut categories = std::collections::HashMap::new();
+        
+        for batch in titles.chunks(50) {
+            let joined = batch.join("|");
+            let mut normalized: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+            let mut listed: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+            let mut clcontinue: Option<String> = None;
+            
+            // `cllimit` counts categories across the whole batch, so a batch can take several requests
+            loop {
+                let mut request = self.client
+                    .get(&api_url)
+                    .query(&[
+                        ("action", "query"),
+                        ("prop", "categories"),
+                        ("titles", &joined),
+                        ("cllimit", "max"),
+                        ("format", "json"),
+                    ])
+                    .header("Accept", "application/json");
+                if let Some(token) = &clcontinue {
+                    request = request.query(&[("clcontinue", token)]);
+                }
+                
+                self.throttle().await;
+                let json: serde_json::Value = request.send().await?.json().await?;
+                // The API reports titles as it normalized them, e.g. with the first letter capitalized
+                normalized.extend(json.pointer("/query/normalized")
+                    .and_then(|n| n.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|n| Some((n.get("from")?.as_str()?.to_string(), n.get("to")?.as_str()?.to_string()))));
+                let pages = json.pointer("/query/pages").and_then(|p| p.as_object());
+                for page in pages.into_iter().flat_map(|pages| pages.values()) {
+                    let Some(title) = page.get("title").and_then(|t| t.as_str()) else { continue };
+                    let names = page.get("categories")
+                        .and_then(|c| c.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|category| category.get("title").and_then(|t| t.as_str()))
+                        .map(|title| title.trim_start_matches("Category:").to_string());
+                    listed.entry(title.to_string()).or_default().extend(names);
+                }
+                
+                clcontinue = json.pointer("/continue/clcontinue")
+                    .and_then(|c| c.as_str())
+                    .map(|c| c.to_string());
+                if clcontinue.is_none() {
+                    break;
+                }
+            }
+            
+            for name in batch {
+                let title = normalized.get(name).unwrap_or(name);
+                if let Some(names) = listed.get(title) {
+                    categories.insert(name.clone(), names.clone());
+                }
+            }
+        }
+        Ok(categories)
+    }
+
+    /// Sets each mod's `last_edited` to the timestamp of its wiki page's
+    /// latest revision (see [`WikiScraper::page_edit_times`]). Returns how
+    /// many mods got a timestamp.
+    pub async fn fill_last_edited(&self, db: &mut ModDatabase) -> Result<usize> {
+        let names: Vec<String> = db.mods.keys().cloned().collect();
+        let edited = self.page_edit_times(&names).await?;
+        let mut updated = 0;
+        for (name, timestamp) in edited {
+            if let Some(mod_info) = db.mods.get_mut(&name) {
+                mod_info.last_edited = Some(timestamp);
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Returns the timestamp of the latest revision of each page in `titles`
+    /// that exists, keyed by the title as given, asking about 50 pages per
+    /// API request.
+    pub async fn page_edit_times(&self, titles: &[String]) -> Result<std::collections::HashMap<String, String>> {
+        let api_url = format!("{}/w/api.php", self.base_url);
+        let mut times = std::collections::HashMap::new();
+        
+        for batch in titles.chunks(50) {
+            self.throttle().await;
+            let json: serde_json::Value = self.client
+                .get(&api_url)
+                .query(&[
+                    ("action", "query"),
+                    ("prop", "revisions"),
+                    ("rvprop", "timestamp"),
+                    ("titles", &batch.join("|")),
+                    ("format", "json"),
+                ])
+                .header("Accept", "application/json")
+                .send()
+                .await?
+                .json()
+                .await?;
+            
+            // The API reports titles as it normalized them, e.g. with the first letter capitalized
+            let normalized: std::collections::HashMap<&str, &str> = json.pointer("/query/normalized")
+                .and_then(|n| n.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|n| Some((n.get("from")?.as_str()?, n.get("to")?.as_str()?)))
+                .collect();
+            let edited: std::collections::HashMap<&str, &str> = json.pointer("/query/pages")
+                .and_then(|p| p.as_object())
+                .into_iter()
+                .flat_map(|pages| pages.values())
+                .filter_map(|page| Some((
+                    page.get("title")?.as_str()?,
+                    page.pointer("/revisions/0/timestamp")?.as_str()?,
+                )))
+                .collect();
+            
+            for name in batch {
+                let title = normalized.get(name.as_str()).copied().unwrap_or(name);
+                if let Some(timestamp) = edited.get(title) {
+                    times.insert(name.clone(), timestamp.to_string());
+                }
+            }
+        }
+        Ok(times)
+    }
+
+    /// Returns the categories of `db` that each of `titles` belongs to: the
+    /// cached mod's categories if it is known, otherwise those the wiki lists
+    /// it under, looked up together. Titles in none of them are left out.
+    async fn mod_categories(
+        &self,
+        db: &ModDatabase,
+        titles: &[String],
+    ) -> std::collections::HashMap<String, Vec<String>> {
+        let mut categories = std::collections::HashMap::new();
+        let mut unknown = Vec::new();
+        for title in titles {
+            match db.find(title) {
+                Some(cached) => {
+                    categories.insert(title.clone(), cached.categories.clone());
+                }
+                None => unknown.push(title.clone()),
+            }
+        }
+        for (title, listed) in self.page_categories(&unknown).await.unwrap_or_default() {
+            categories.insert(title, listed.into_iter().filter(|c| db.categories.contains_key(c)).collect());
+        }
+        categories.retain(|_, categories| !categories.is_empty());
+        categories
+    }
+
+    /// Scrapes the `limit` most recently created or edited mod pages, newest
+    /// first, stopping at edits older than `since` when given, or after
+    /// [`RECENT_MODS_MAX_PAGES`] pages of changes. Pages outside `db`'s
+    /// categories are skipped. Each mod's `last_edited` is the time of its
+    /// latest edit.
+    pub async fn recent_mods(
+        &self,
+        db: &ModDatabase,
+        limit: usize,
+        since: Option<DateTime<Utc>>,
+        verbose: bool,
+    ) -> Result<Vec<ModInfo>> {
+        let api_url = format!("{}/w/api.php", self.base_url);
+        let rcend = since.map(|since| since.to_rfc3339_opts(SecondsFormat::Secs, true));
+        let mut seen: Vec<String> = Vec::new();
+        let mut mods: Vec<ModInfo> = Vec::new();
+        let mut rccontinue: Option<String> = None;
+        
+        for _ in 0..RECENT_MODS_MAX_PAGES {
+            let mut request = self.client
+                .get(&api_url)
+                .query(&[
+                    ("action", "query"),
+                    ("list", "recentchanges"),
+                    ("rcnamespace", "0"),
+                    ("rctype", "new|edit"),
+                    ("rcprop", "title|timestamp"),
+                    ("rclimit", "50"),
+                    ("format", "json"),
+                ])
+                .header("Accept", "application/json");
+            if let Some(rcend) = &rcend {
+                request = request.query(&[("rcend", rcend)]);
+            }
+            if let Some(token) = &rccontinue {
+                request = request.query(&[("rccontinue", token)]);
+            }
+            
+            self.throttle().await;
+            let json: serde_json::Value = request.send().await?.json().await?;
+            let mut changes: Vec<(String, Option<String>)> = Vec::new();
+            for change in json.pointer("/query/recentchanges").and_then(|c| c.as_array()).into_iter().flatten() {
+                let Some(title) = change.get("title").and_then(|t| t.as_str()) else { continue };
+                if !seen.iter().any(|t| t == title) {
+                    seen.push(title.to_string());
+                    let timestamp = change.get("timestamp").and_then(|t| t.as_str()).map(|t| t.to_string());
+                    changes.push((title.to_string(), timestamp));
+                }
+            }
+            
+            let titles: Vec<String> = changes.iter().map(|(title, _)| title.clone()).collect();
+            let mut categories = self.mod_categories(db, &titles).await;
+            for (title, timestamp) in changes {
+                if mods.len() >= limit {
+                    break;
+                }
+                let Some(categories) = categories.remove(&title) else { continue };
+                match self.scrape_mod_page(&title).await {
+                    Ok(mut mod_info) => {
+                        if verbose {
+                            println!("  ✓ {}", mod_info.name);
+                        }
+                        mod_info.categories = categories;
+                        if let Some(timestamp) = timestamp {
+                            mod_info.last_edited = Some(timestamp);
+                        }
+                        mods.push(mod_info);
+                    }
+                    Err(e) => {
+                        if verbose {
+                            eprintln!("  ✗ Failed to scrape {}: {}", title, e);
+                        }
+                    }
+                }
+            }
+            
+            rccontinue = json.pointer("/continue/rccontinue")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_string());
+            if rccontinue.is_none() || mods.len() >= limit {
+                break;
+            }
+        }
+        
+        Ok(mods)
+    }
+
+    /// Re-scrapes the pages changed on the wiki since `since` into `db`:
+    /// cached mods are refreshed, and new pages in one of `db`'s categories
+    /// are added. Returns the number of mods refreshed or added, and the
+    /// pages that couldn't be scraped. The database is only marked as
+    /// current if every page was scraped.
+    pub async fn update_changed_since(
+        &self,
+        db: &mut ModDatabase,
+        since: DateTime<Utc>,
+        verbose: bool,
+    ) -> Result<(usize, UpdateFailures)> {
+        let changed = self.changed_pages_since(since).await?;
+        if verbose {
+            println!("Found {} pages changed since {}", changed.len(), since.to_rfc3339());
+        }
+        
+        let mut fresh = ModDatabase::new();
+        let mut failures = UpdateFailures::default();
+        let mut categories = self.mod_categories(db, &changed).await;
+        for title in changed {
+            let Some(categories) = categories.remove(&title) else { continue };
+            
+            match self.scrape_mod_with_releases(&title).await {
+                Ok(mut mod_info) => {
+                    if verbose {
+                        println!("  ✓ {}", mod_info.name);
+                    }
+                    mod_info.categories = categories;
+                    fresh.mods.insert(mod_info.name.clone(), mod_info);
+                }
+                Err(e) => {
+                    if let Some(line) = self.mod_failure(&mut failures, &title, e)?.filter(|_| verbose) {
+                        eprintln!("{}", line);
+                    }
+                }
+            }
+        }
+        fresh.carry_over_timestamps(db);
+        
+        let refreshed = fresh.mods.len();
+        for (name, mod_info) in fresh.mods {
+            for category in &mod_info.categories {
+                let cat_mods = db.categories.entry(category.clone()).or_default();
+                if !cat_mods.contains(&name) {
+                    cat_mods.push(name.clone());
+                }
+            }
+            db.mods.insert(name, mod_info);
+        }
+        db.clear_search_index();
+        
+        // Every category is now as current as a full crawl would make it,
+        // unless some changed pages are still missing
+        if failures.is_empty() {
+            let now = Utc::now().to_rfc3339();
+            for category in db.categories.keys() {
+                db.category_updated.insert(category.clone(), now.clone());
+            }
+            db.last_updated = now;
+        }
+        Ok((refreshed, failures))
+    }
+
+    /// Sends a HEAD request to the wiki root and returns the HTTP status code.
+    pub async fn ping(&self) -> Result<u16> {
+        self.throttle().await;
+        let response = self.client.head(&self.base_url).send().await?;
+        Ok(response.status().as_u16())
+    }
+
+    /// Asks the MediaWiki API whether a page titled `title` exists.
+    pub async fn page_exists(&self, title: &str) -> Result<bool> {
+        let api_url = format!("{}/w/api.php", self.base_url);
+        self.throttle().await;
+        let json: serde_json::Value = self.client
+            .get(&api_url)
+            .query(&[("action", "query"), ("titles", title), ("format", "json")])
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .json()
+            .await?;
+        
+        let pages = json.pointer("/query/pages")
+            .and_then(|p| p.as_object())
+            .ok_or_else(|| anyhow!("Unexpected API response when checking '{}'", title))?;
+        Ok(pages.values().any(|page| page.get("missing").is_none() && page.get("invalid").is_none()))
+    }
+
+    /// Asks the GitHub API whether the repository at `github_url` has
+    /// published at least one Release (see [`WikiScraper::github_json`]).
+    pub async fn has_github_releases(&self, github_url: &str) -> Result<bool> {
+        let repo = github::repo_path(github_url)
+            .ok_or_else(|| anyhow!("'{}' is not a GitHub repository URL", github_url))?;
+        let releases = self.github_json(&format!("/repos/{}/releases?per_page=1", repo)).await?;
+        let releases = releases.as_array()
+            .ok_or_else(|| anyhow!("GitHub returned no release list for {}", repo))?;
+        Ok(!releases.is_empty())
+    }
+
+    /// Looks up `has_github_releases` for every mod with a GitHub URL whose
+    /// check is due (see [`ModInfo::releases_check_due`]). Mods whose lookup
+    /// fails keep what they had. Returns the number of mods that were resolved.
+    pub async fn fill_github_releases(&self, db: &mut ModDatabase) -> usize {
+        let mut handles = Vec::new();
+        for mod_info in db.mods.values().filter(|m| m.releases_check_due()) {
+            if let Some(github_url) = mod_info.github_url.clone() {
+                let scraper = self.clone();
+                let name = mod_info.name.clone();
+                handles.push(tokio::spawn(async move {
+                    (name, scraper.has_github_releases(&github_url).await)
+                }));
+            }
+        }
+        
+        let mut resolved = 0;
+        for handle in handles {
+            if let Ok((name, Ok(has_releases))) = handle.await {
+                if let Some(mod_info) = db.mods.get_mut(&name) {
+                    mod_info.has_github_releases = Some(has_releases);
+                    mod_info.releases_checked = Some(Utc::now().to_rfc3339());
+                    resolved += 1;
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Asks the GitHub API for the stargazer count of the repository at
+    /// `github_url` (see [`WikiScraper::github_json`]).
+    pub async fn github_stars(&self, github_url: &str) -> Result<u32> {
+        let repo = github::repo_path(github_url)
+            .ok_or_else(|| anyhow!("'{}' is not a GitHub repository URL", github_url))?;
+        let json = self.github_json(&format!("/repos/{}", repo)).await?;
+        json.get("stargazers_count")
+            .and_then(|c| c.as_u64())
+            .map(|c| c as u32)
+            .ok_or_else(|| anyhow!("GitHub returned no stargazer count for {}", repo))
+    }
+
+    /// Fetches `stars` for every mod with a GitHub URL, replacing cached
+    /// counts. Mods whose lookup fails keep their previous count. Returns
+    /// the number of mods that were updated.
+    pub async fn fill_github_stars(&self, db: &mut ModDatabase) -> usize {
+        let mut handles = Vec::new();
+        for mod_info in db.mods.values() {
+            if let Some(github_url) = mod_info.github_url.clone() {
+                let scraper = self.clone();
+                let name = mod_info.name.clone();
+                handles.push(tokio::spawn(async move {
+                    (name, scraper.github_stars(&github_url).await)
+                }));
+            }
+        }
+        
+        let mut updated = 0;
+        for handle in handles {
+            if let Ok((name, Ok(stars))) = handle.await {
+                if let Some(mod_info) = db.mods.get_mut(&name) {
+                    mod_info.stars = Some(stars);
+                    updated += 1;
+                }
+            }
+        }
+        updated
+    }
+
+    /// Sends a HEAD request to `url`, following redirects, and reports
+    /// whether it resolves. A redirect that ends in a 404 or 410 is broken.
+    /// Other error statuses, such as rate limiting, are errors.
+    pub async fn check_link(&self, url: &str) -> Result<LinkStatus> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self.client.head(url).send().await?;
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+            return Ok(LinkStatus::Broken(status.as_u16()));
+        }
+        let response = response.error_for_status()?;
+        if response.url().as_str() != url {
+            return Ok(LinkStatus::Redirected(response.url().to_string()));
+        }
+        Ok(LinkStatus::Ok)
+    }
+
+    /// Checks every mod's GitHub URL with [`WikiScraper::check_link`],
+    /// at most `concurrency` at a time. Returns `(mod name, url, result)`
+    /// sorted by mod name.
+    pub async fn check_github_links(&self, db: &ModDatabase, concurrency: usize) -> Vec<(String, String, Result<LinkStatus>)> {
+        let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+        let mut tasks = tokio::task::JoinSet::new();
+        for mod_info in db.mods.values() {
+            let Some(url) = mod_info.github_url.clone() else {
+                continue;
+            };
+            let scraper = self.clone();
+            let permits = permits.clone();
+            let name = mod_info.name.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let result = scraper.check_link(&url).await;
+                (name, url, result)
+            });
+        }
+        
+        let mut results = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(result) = joined {
+                results.push(result);
+            }
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+
+    /// Scrapes a mod page and, if it links to GitHub, whether the repository has Releases.
+    async fn scrape_mod_with_releases(&self, mod_name: &str) -> Result<ModInfo> {
+        let mut mod_info = self.scrape_mod_page(mod_name).await?;
+        if let Some(github_url) = &mod_info.github_url {
+            if let Ok(has_releases) = self.has_github_releases(github_url).await {
+                mod_info.has_github_releases = Some(has_releases);
+                mod_info.releases_checked = Some(Utc::now().to_rfc3339());
+            }
+        }
+        Ok(mod_info)
+    }
+
+    /// Re-scrapes one category's members into `db`, leaving other categories
+    /// untouched. `category` is listed from its configured wiki category, or
+    /// from the wiki category of the same name if it isn't configured. Mods
+    /// that left the category lose it, and are dropped if it was their only
+    /// category; mods still listed whose page couldn't be scraped keep their
+    /// cached entry. Returns the mod pages that couldn't be scraped.
+    pub async fn update_category(&self, db: &mut ModDatabase, category: &str, verbose: bool) -> Result<UpdateFailures> {
+        if verbose {
+            println!("Collecting mods from category: {}", category);
+        }
+        let wiki_category = self.categories.wiki_category(category).unwrap_or(category);
+        let mod_names = self.scrape_category_page_all(wiki_category).await?;
+        
+        let mut handles = Vec::new();
+        for mod_name in mod_names.clone() {
+            let scraper = self.clone();
+            handles.push(tokio::spawn(async move {
+                let result = scraper.scrape_mod_with_releases(&mod_name).await;
+                (mod_name, result)
+            }));
+        }
+        
+        let mut fresh = ModDatabase::new();
+        let mut failures = UpdateFailures::default();
+        let mut not_mods = Vec::new();
+        for handle in handles {
+            match handle.await {
+                Ok((_, Ok(mut mod_info))) => {
+                    if verbose {
+                        println!("  ✓ {}", mod_info.name);
+                    }
+                    mod_info.categories = db.mods.get(&mod_info.name)
+                        .map(|existing| existing.categories.clone())
+                        .unwrap_or_default();
+                    if !mod_info.categories.iter().any(|c| c == category) {
+                        mod_info.categories.push(category.to_string());
+                    }
+                    fresh.mods.insert(mod_info.name.clone(), mod_info);
+                }
+                Ok((mod_name, Err(e))) => {
+                    if e.is::<NotAModPage>() {
+                        not_mods.push(mod_name.clone());
+                    }
+                    if let Some(line) = self.mod_failure(&mut failures, &mod_name, e)?.filter(|_| verbose) {
+                        eprintln!("{}", line);
+                    }
+                }
+                Err(e) => {
+                    if let Some(line) = self.task_failure(&mut failures, e)?.filter(|_| verbose) {
+                        eprintln!("{}", line);
+                    }
+                }
+            }
+        }
+        fresh.carry_over_timestamps(db);
+        
+        // Mods still listed whose page couldn't be scraped keep their cached entry
+        let kept: Vec<String> = mod_names.into_iter()
+            .filter(|name| !fresh.mods.contains_key(name) && !not_mods.contains(name) && db.mods.contains_key(name))
+            .collect();
+        
+        // Drop the category from mods no longer listed under it
+        for old_name in db.categories.remove(category).unwrap_or_default() {
+            if fresh.mods.contains_key(&old_name) || kept.contains(&old_name) {
+                continue;
+            }
+            if let Some(mod_info) = db.mods.get_mut(&old_name) {
+                mod_info.categories.retain(|c| c != category);
+                if mod_info.categories.is_empty() {
+                    db.mods.remove(&old_name);
+                }
+            }
+        }
+        
+        let mut members: Vec<String> = fresh.mods.keys().chain(&kept).cloned().collect();
+        members.sort();
+        db.categories.insert(category.to_string(), members);
+        db.mods.extend(fresh.mods);
+        db.clear_search_index();
+        db.category_updated.insert(category.to_string(), Utc::now().to_rfc3339());
+        Ok(failures)
+    }
+
+    /// Crawls every known category and mod page into a fresh database.
+    pub async fn update_database(&self) -> Result<ModDatabase> {
+        self.update_database_with_verbosity(true).await
+    }
+    
+    /// Like [`WikiScraper::update_database`], optionally logging progress to stdout.
+    pub async fn update_database_with_verbosity(&self, verbose: bool) -> Result<ModDatabase> {
+        self.update_database_with_progress(verbose, |_| {}).await
+    }
+    
+    /// Like [`WikiScraper::update_database_with_verbosity`], calling
+    /// `on_progress` after each mod page is scraped or fails.
+    pub async fn update_database_with_progress<F>(&self, verbose: bool, on_progress: F) -> Result<ModDatabase>
+    where
+        F: FnMut(&UpdateProgress),
+    {
+        let (db, _, _) = self.update_database_streaming(verbose, on_progress, |_| {}).await?;
+        Ok(db)
+    }
+    
+    /// Like [`WikiScraper::update_database_with_progress`], also calling
+    /// `on_mod` with each mod as soon as its page is scraped, in completion
+    /// order. A mod reached through several redirects is passed once per page,
+    /// each time with the categories merged so far. Also returns how long
+    /// each phase of the crawl took, and what couldn't be scraped.
+    pub async fn update_database_streaming<F, M>(
+        &self,
+        verbose: bool,
+        mut on_progress: F,
+        mut on_mod: M,
+    ) -> Result<(ModDatabase, UpdateTimings, UpdateFailures)>
+    where
+        F: FnMut(&UpdateProgress),
+        M: FnMut(&ModInfo),
+    {
+        let mut db = ModDatabase::new();
+        let mut timings = UpdateTimings::default();
+        let mut failures = UpdateFailures::default();
+        
+        let categories: Vec<(&str, &str)> = self.categories.iter()
+            .map(|category| (category.name.as_str(), category.wiki_category.as_str()))
+            .collect();
+
+        // Collect all mod names from all categories first, fetching the
+        // listings concurrently since they're independent
+        let listing_started = Instant::now();
+        let mut listing_tasks = tokio::task::JoinSet::new();
+        for (index, (category_name, wiki_category)) in categories.iter().enumerate() {
+            if verbose {
+                println!("Collecting mods from category: {}", category_name);
+            }
+            let scraper = self.clone();
+            let wiki_category = wiki_category.to_string();
+            listing_tasks.spawn(async move {
+                let result = scraper
+                    .scrape_category_page_with_verbosity(&wiki_category, scraper.category_page_size, verbose)
+                    .await;
+                (index, result)
+            });
+        }
+        let mut listings: Vec<Option<Result<Vec<String>>>> = categories.iter().map(|_| None).collect();
+        while let Some(joined) = listing_tasks.join_next().await {
+            let (index, result) = joined?;
+            listings[index] = Some(result);
+        }
+        timings.categories = listing_started.elapsed();
+        
+        // Merge in category order so each mod's categories stay in a stable order
+        let mut all_mod_names = std::collections::HashSet::new();
+        let mut mod_categories: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+        for ((category_name, _), listing) in categories.iter().zip(listings) {
+            match listing.expect("every category listing task reports back") {
+                Ok(mod_names) => {
+                    db.category_updated.insert(category_name.to_string(), Utc::now().to_rfc3339());
+                    for mod_name in mod_names {
+                        all_mod_names.insert(mod_name.clone());
+                        mod_categories.entry(mod_name).or_default().push(category_name.to_string());
+                    }
+                }
+                Err(e) => {
+                    if self.strict {
+                        return Err(e.context(format!("Failed to scrape category {}", category_name)));
+                    }
+                    if !self.quiet_errors {
+                        eprintln!("Failed to scrape category {}: {}", category_name, e);
+                    }
+                    failures.categories.push(category_name.to_string());
+                }
+            }
+        }
+        
+        if verbose {
+            println!("Processing {} unique mods concurrently...", all_mod_names.len());
+        }
+        
+        // One cheap batch of edit times stamps each mod's `last_edited` and,
+        // with a previous crawl, decides which pages need scraping
+        let titles: Vec<String> = all_mod_names.iter().cloned().collect();
+        let (edit_times, edit_times_warning) = match self.page_edit_times(&titles).await {
+            Ok(times) => (times, None),
+            Err(e) => {
+                let consequence = if self.previous.is_some() { ", refetching every page" } else { "" };
+                let line = format!("⚠️  Could not check page edit times{}: {}", consequence, e);
+                (std::collections::HashMap::new(), (!self.quiet_errors).then_some(line))
+            }
+        };
+        let mut unchanged = std::collections::HashMap::new();
+        if let Some(previous) = &self.previous {
+            for (title, edited) in &edit_times {
+                if let Some(cached) = previous.mods.get(title).filter(|cached| is_unchanged(cached, edited)) {
+                    unchanged.insert(title.clone(), cached.clone());
+                }
+            }
+        }
+        
+        // Process all mods concurrently
+        let pages_started = Instant::now();
+        let mut tasks = tokio::task::JoinSet::new();
+        for mod_name in all_mod_names.iter() {
+            let scraper = self.clone();
+            let name = mod_name.clone();
+            let cached = unchanged.remove(&name);
+            tasks.spawn(THROTTLED.scope(Cell::new(Duration::ZERO), async move {
+                let started = Instant::now();
+                let (result, reused) = match cached {
+                    Some(cached) => (Ok(cached), true),
+                    None => (scraper.scrape_mod_with_releases(&name).await, false),
+                };
+                let elapsed = started.elapsed().saturating_sub(THROTTLED.with(Cell::get));
+                (name, result, elapsed, reused)
+            }));
+        }
+        
+        // Collect results and organize by category
+        let mut category_mods: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+        for (category_name, _) in &categories {
+            category_mods.insert(category_name.to_string(), Vec::new());
+        }
+        
+        // On a terminal, a progress bar replaces the per-mod log lines;
+        // redirected output keeps the line-by-line log so it stays parseable
+        let bar = (verbose && std::io::stdout().is_terminal()).then(|| {
+            let bar = ProgressBar::new(tasks.len() as u64);
+            bar.set_style(
+                ProgressStyle::with_template("  {bar:40.cyan/blue} {pos}/{len} {wide_msg}")
+                    .expect("valid progress bar template")
+                    .progress_chars("█▉▊▋▌▍▎▏ "),
+            );
+            bar
+        });
+        let log_mod_lines = verbose && bar.is_none();
+        if let Some(line) = edit_times_warning {
+            if let Some(bar) = &bar {
+                bar.println(line);
+            } else if verbose {
+                eprintln!("{}", line);
+            }
+        }
+        
+        let mut progress = UpdateProgress::new(tasks.len());
+        while let Some(outcome) = tasks.join_next().await {
+            let outcome = outcome.map(|(mod_name, result, elapsed, reused)| {
+                if reused {
+                    progress.unchanged += 1;
+                } else {
+                    timings.per_mod.push((mod_name.clone(), elapsed));
+                }
+                (mod_name, result)
+            });
+            progress.record(&outcome);
+            on_progress(&progress);
+            
+            match outcome {
+                Ok((mod_name, result)) => {
+                    match result {
+                        Ok(mut mod_info) => {
+                            if let Some(edited) = edit_times.get(&mod_name) {
+                                mod_info.last_edited = Some(edited.clone());
+                            }
+                            if let Some(categories) = mod_categories.get(&mod_name) {
+                                mod_info.categories = categories.clone();
+                                // Redirects resolve to their target's name; merge rather than duplicate
+                                if let Some(existing) = db.mods.get(&mod_info.name) {
+                                    for category in &existing.categories {
+                                        if !mod_info.categories.contains(category) {
+                                            mod_info.categories.push(category.clone());
+                                        }
+                                    }
+                                }
+                                for category in &mod_info.categories {
+                                    if let Some(cat_mods) = category_mods.get_mut(category) {
+                                        if !cat_mods.contains(&mod_info.name) {
+                                            cat_mods.push(mod_info.name.clone());
+                                        }
+                                    }
+                                }
+                                on_mod(&mod_info);
+                                db.mods.insert(mod_info.name.clone(), mod_info);
+                                if log_mod_lines {
+                                    println!("  ✓ {}", mod_name);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            if let Some(line) = self.mod_failure(&mut failures, &mod_name, e)? {
+                                if let Some(bar) = &bar {
+                                    bar.println(line);
+                                } else if verbose {
+                                    eprintln!("{}", line);
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    if let Some(line) = self.task_failure(&mut failures, e)? {
+                        if let Some(bar) = &bar {
+                            bar.println(line);
+                        } else if verbose {
+                            eprintln!("{}", line);
+                        }
+                    }
+                }
+            }
+            
+            if let Some(bar) = &bar {
+                bar.set_message(progress.current.clone());
+                bar.inc(1);
+            }
+            
+            if let Some((path, every)) = &self.checkpoint {
+                if progress.completed.is_multiple_of(*every) && progress.completed < progress.total {
+                    if let Err(e) = save_checkpoint(&db, &category_mods, path) {
+                        let line = format!("⚠️  Could not save partial update: {}", e);
+                        match &bar {
+                            Some(bar) => bar.println(line),
+                            None => eprintln!("{}", line),
+                        }
+                    }
+                }
+            }
+        }
+        
+        if let Some(bar) = bar {
+            bar.finish_and_clear();
+        }
+        timings.mod_pages = pages_started.elapsed();
+        
+        db.categories = category_mods;
+        Ok((db, timings, failures))
+    }
+}
+
+/// What an update couldn't scrape. Pages that turned out not to be mods
+/// aren't failures.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateFailures {
+    /// Categories whose listing couldn't be fetched.
+    pub categories: Vec<String>,
+    /// Mod pages that couldn't be scraped, by title.
+    pub mods: Vec<String>,
+    /// Scrape tasks that panicked, whose page is unknown.
+    pub tasks: usize,
+}
+
+impl UpdateFailures {
+    /// Number of categories, mod pages, and tasks that failed.
+    pub fn len(&self) -> usize {
+        self.categories.len() + self.mods.len() + self.tasks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns true if `cached` was scraped no earlier than `edited`, the time
+/// of its page's latest revision.
+fn is_unchanged(cached: &ModInfo, edited: &str) -> bool {
+    let cached = cached.last_edited.as_deref().map(DateTime::parse_from_rfc3339);
+    match (cached, DateTime::parse_from_rfc3339(edited)) {
+        (Some(Ok(cached)), Ok(edited)) => cached >= edited,
+        _ => false,
+    }
+}
+
+/// Writes a partly crawled database to `path`, dated as never updated so
+/// that every category counts as stale.
+fn save_checkpoint(
+    db: &ModDatabase,
+    categories: &std::collections::HashMap<String, Vec<String>>,
+    path: &Path,
+) -> Result<()> {
+    let mut partial = ModDatabase::new();
+    partial.mods = db.mods.clone();
+    partial.categories = categories.clone();
+    partial.last_updated = DateTime::<Utc>::UNIX_EPOCH.to_rfc3339();
+    partial.save_to(path)
+}
+
+/// How long the phases of a full update took (see `update --timings`).
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTimings {
+    /// Fetching every category listing.
+    pub categories: Duration,
+    /// Wall time from starting the mod page requests to the last response.
+    pub mod_pages: Duration,
+    /// Time to scrape each mod page, by page title, in completion order.
+    /// Time spent waiting for the rate limiter isn't counted.
+    pub per_mod: Vec<(String, Duration)>,
+}
+
+impl UpdateTimings {
+    /// Mean time to scrape one mod page.
+    pub fn average(&self) -> Option<Duration> {
+        let total: Duration = self.per_mod.iter().map(|(_, elapsed)| *elapsed).sum();
+        (!self.per_mod.is_empty()).then(|| total / self.per_mod.len() as u32)
+    }
+
+    /// Median time to scrape one mod page.
+    pub fn median(&self) -> Option<Duration> {
+        let mut durations: Vec<Duration> = self.per_mod.iter().map(|(_, elapsed)| *elapsed).collect();
+        durations.sort();
+        let mid = durations.len() / 2;
+        match durations.len() {
+            0 => None,
+            n if n % 2 == 0 => Some((durations[mid - 1] + durations[mid]) / 2),
+            _ => Some(durations[mid]),
+        }
+    }
+
+    /// The `n` slowest mod pages, slowest first.
+    pub fn slowest(&self, n: usize) -> Vec<&(String, Duration)> {
+        let mut slowest: Vec<&(String, Duration)> = self.per_mod.iter().collect();
+        slowest.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        slowest.truncate(n);
+        slowest
+    }
+}
+
+/// Progress through the mod-page phase of an update.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateProgress {
+    /// Number of mod pages to scrape.
+    pub total: usize,
+    /// Pages processed so far, including failures.
+    pub completed: usize,
+    pub failed: usize,
+    /// Pages that turned out not to be mods (see [`NotAModPage`]); not counted as failed.
+    pub skipped: usize,
+    /// Pages not edited since the previous crawl, whose cached mod was
+    /// reused instead (see [`WikiScraper::with_previous`]).
+    pub unchanged: usize,
+    /// The mod page most recently processed.
+    pub current: String,
+    pub percent: u32,
+}
+
+impl UpdateProgress {
+    fn new(total: usize) -> Self {
+        Self { total, completed: 0, failed: 0, skipped: 0, unchanged: 0, current: String::new(), percent: 0 }
+    }
+
+    fn record(&mut self, outcome: &Result<(String, Result<ModInfo>), tokio::task::JoinError>) {
+        self.completed += 1;
+        match outcome {
+            Ok((name, result)) => {
+                self.current = name.clone();
+                match result {
+                    Err(e) if e.is::<NotAModPage>() => self.skipped += 1,
+                    Err(_) => self.failed += 1,
+                    Ok(_) => {}
+                }
+            }
+            Err(_) => self.failed += 1,
+        }
+        self.percent = (self.completed * 100 / self.total.max(1)) as u32;
+    }
+}
+
+/// Outcome of [`WikiScraper::check_link`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkStatus {
+    /// The link answered with a success status at its own URL.
+    Ok,
+    /// The link redirected to this URL, which answered with a success status.
+    Redirected(String),
+    /// The link, or the page it redirected to, answered with this status (404 or 410).
+    Broken(u16),
+}
+
+/// Returned by [`WikiScraper::scrape_mod_page`] for a page that isn't a mod,
+/// such as a disambiguation page or a stub. Updates skip these rather than
+/// counting them as failures.
+#[derive(Debug)]
+pub struct NotAModPage(pub String);
+
+impl std::fmt::Display for NotAModPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a mod page", self.0)
+    }
+}
+
+impl std::error::Error for NotAModPage {}
+
+/// Namespaces whose pages can show up in category listings but are never mods.
+const NON_MOD_NAMESPACES: &[&str] = &[
+    "Category", "File", "Template", "Help", "User", "Talk", "Project", "Balatro Wiki", "Balatro Mods Wiki",
+    "MediaWiki", "Module", "Special",
+];
+
+/// Returns true if `title` is in a namespace that never holds mods, including
+/// any talk namespace.
+fn is_non_mod_namespace(title: &str) -> bool {
+    title.split_once(':').is_some_and(|(namespace, _)| {
+        NON_MOD_NAMESPACES.iter().any(|ns| ns.eq_ignore_ascii_case(namespace)) || namespace.ends_with(" talk")
+    })
+}
+
+// Selectors for the parts of a MediaWiki page the scraper reads
+static TITLE: LazySelector = LazySelector::new("h1.firstHeading");
+static GITHUB_LINKS: LazySelector = LazySelector::new("a[href*='github.com']");
+static INFOBOX: LazySelector = LazySelector::new(".infobox");
+static INFOBOX_ROWS: LazySelector = LazySelector::new(".infobox tr");
+static INFOBOX_CELLS: LazySelector = LazySelector::new("th, td");
+static INFOBOX_LINKS: LazySelector = LazySelector::new(".infobox a[href]");
+static INFOBOX_IMAGES: LazySelector = LazySelector::new(".infobox img");
+static NON_MOD_MARKERS: LazySelector = LazySelector::new("#disambigbox, .disambig, .dmbox, .mw-disambig, .stub, #stub");
+static CATEGORY_LINKS: LazySelector = LazySelector::new("#catlinks");
+static CONTENT: LazySelector = LazySelector::new(".mw-parser-output");
+static CONTENT_CHILDREN: LazySelector = LazySelector::new(".mw-parser-output > *");
+static LINKS: LazySelector = LazySelector::new("a[href]");
+static REDIRECT_LINKS: LazySelector = LazySelector::new(".redirectMsg a, .redirectText a");
+static REDIRECTED_FROM: LazySelector = LazySelector::new(".mw-redirectedfrom");
+static CANONICAL_LINK: LazySelector = LazySelector::new("link[rel='canonical']");
+static KUDOS_COUNTS: LazySelector = LazySelector::new(
+    ".mw-ext-score, .voteboxes .votebox-count, .vote-count, .kudos-count, [class*='thumbs-up'] .count",
+);
+
+/// Returns true for pages without an infobox that are marked as a
+/// disambiguation page or a stub.
+fn is_non_mod_page(document: &Html) -> Result<bool> {
+    if document.select(INFOBOX.get()?).next().is_some() {
+        return Ok(false);
+    }
+    if document.select(NON_MOD_MARKERS.get()?).next().is_some() {
+        return Ok(true);
+    }
+    let in_marker_category = document.select(CATEGORY_LINKS.get()?).any(|catlinks| {
+        let text = catlinks.text().collect::<String>().to_lowercase();
+        text.contains("disambiguation") || text.contains("stubs")
+    });
+    let refers_elsewhere = document.select(CONTENT.get()?)
+        .any(|content| content.text().collect::<String>().contains("may refer to:"));
+    Ok(in_marker_category || refers_elsewhere)
+}
+
+/// The log line for a mod page that couldn't be turned into a mod.
+fn scrape_failure_line(mod_name: &str, error: &anyhow::Error) -> String {
+    if error.is::<NotAModPage>() {
+        format!("  ⚠️  Skipped {}: not a mod page", mod_name)
+    } else {
+        format!("  ✗ Failed to scrape {}: {}", mod_name, error)
+    }
+}
+
+/// Returns the absolute target URL if `html` is an unfollowed redirect page.
+fn redirect_target(html: &str, base_url: &str) -> Result<Option<String>> {
+    let document = Html::parse_document(html);
+    Ok(document
+        .select(REDIRECT_LINKS.get()?)
+        .next()
+        .and_then(|el| el.value().attr("href"))
+        .map(|href| absolute_wiki_url(href, base_url)))
+}
+
+/// Returns the canonical page URL if MediaWiki reports that it followed a redirect.
+fn redirected_canonical_url(document: &Html, base_url: &str) -> Result<Option<String>> {
+    if document.select(REDIRECTED_FROM.get()?).next().is_none() {
+        return Ok(None);
+    }
+    
+    Ok(document
+        .select(CANONICAL_LINK.get()?)
+        .next()
+        .and_then(|el| el.value().attr("href"))
+        .map(|href| absolute_wiki_url(href, base_url)))
+}
+
+/// Returns the page's vote count from a voting/kudos extension widget, if
+/// the wiki has one installed.
+fn kudos_count(document: &Html) -> Result<Option<u32>> {
+    Ok(document
+        .select(KUDOS_COUNTS.get()?)
+        .filter_map(|el| {
+            let digits: String = el.text().collect::<String>().chars().filter(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        })
+        .next())
+}
+
+/// Returns the text of the value cell in the first infobox row whose header
+/// starts with `label` (e.g. "Author" or "Author(s)" for "author"), with
+/// whitespace collapsed. `None` if there is no such row or it is empty.
+fn infobox_value(document: &Html, label: &str) -> Result<Option<String>> {
+    let cell_selector = INFOBOX_CELLS.get()?;
+    for row in document.select(INFOBOX_ROWS.get()?) {
+        let mut cells = row.select(cell_selector);
+        let Some(header) = cells.next() else { continue };
+        if !header.text().collect::<String>().trim().to_lowercase().starts_with(label) {
+            continue;
+        }
+        let value = cells.next().map(|cell| cell.text().collect::<String>()).unwrap_or_default();
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        return Ok((!value.is_empty()).then_some(value));
+    }
+    Ok(None)
+}
+
+/// Collects the values of infobox rows headed "Type" or "Tags", split on
+/// commas and slashes, e.g. "Joker / Deck" becomes `["Joker", "Deck"]`.
+fn infobox_tags(document: &Html) -> Result<Vec<String>> {
+    let cell_selector = INFOBOX_CELLS.get()?;
+    let mut tags: Vec<String> = Vec::new();
+    
+    for row in document.select(INFOBOX_ROWS.get()?) {
+        let mut cells = row.select(cell_selector);
+        let Some(header) = cells.next() else { continue };
+        let header = header.text().collect::<String>().trim().to_lowercase();
+        if !header.starts_with("type") && !header.starts_with("tag") {
+            continue;
+        }
+        for value in cells {
+            let text = value.text().collect::<String>();
+            for tag in text.split([',', '/']) {
+                let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                    tags.push(tag);
+                }
+            }
+        }
+    }
+    
+    Ok(tags)
+}
+
+/// Hosts whose links are treated as mod downloads.
+const DOWNLOAD_HOSTS: &[&str] = &["gamebanana.com", "drive.google.com", "nexusmods.com"];
+
+/// Collects download links from the infobox and from any section headed
+/// "Download…", keeping those on [`DOWNLOAD_HOSTS`] or pointing at a `.zip`.
+fn download_urls(document: &Html, base_url: &str) -> Result<Vec<String>> {
+    let link_selector = LINKS.get()?;
+    let mut hrefs: Vec<&str> = document.select(INFOBOX_LINKS.get()?)
+        .filter_map(|a| a.value().attr("href"))
+        .collect();
+    let mut in_download_section = false;
+    for element in document.select(CONTENT_CHILDREN.get()?) {
+        let name = element.value().name();
+        let is_heading = matches!(name, "h2" | "h3" | "h4" | "h5" | "h6")
+            || element.value().classes().any(|class| class == "mw-heading");
+        if is_heading {
+            in_download_section = element.text().collect::<String>().to_lowercase().contains("download");
+        } else if in_download_section {
+            hrefs.extend(element.select(link_selector).filter_map(|a| a.value().attr("href")));
+        }
+    }
+    
+    let mut urls: Vec<String> = Vec::new();
+    for href in hrefs {
+        let lower = href.to_lowercase();
+        let is_download = DOWNLOAD_HOSTS.iter().any(|host| lower.contains(host))
+            || lower.split(['?', '#']).next().is_some_and(|path| path.ends_with(".zip"));
+        let url = absolute_wiki_url(href, base_url);
+        if is_download && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    Ok(urls)
+}
+
+/// Returns the absolute URL of the infobox's cover image, skipping tiny
+/// icons such as edit pencils and flag sprites.
+fn infobox_image_url(document: &Html, base_url: &str) -> Result<Option<String>> {
+    const MIN_IMAGE_SIZE: u32 = 48;
+    
+    Ok(document
+        .select(INFOBOX_IMAGES.get()?)
+        .filter(|img| {
+            let too_small = ["width", "height"].iter().any(|attr| {
+                img.value()
+                    .attr(attr)
+                    .and_then(|v| v.trim_end_matches("px").parse::<u32>().ok())
+                    .is_some_and(|size| size < MIN_IMAGE_SIZE)
+            });
+            !too_small
+        })
+        .filter_map(|img| img.value().attr("src"))
+        .find(|src| {
+            let src = src.to_lowercase();
+            !src.contains("pencil") && !src.contains("/icons/") && !src.ends_with(".svg")
+        })
+        .map(|href| absolute_wiki_url(href, base_url)))
+}
+
+fn absolute_wiki_url(href: &str, base_url: &str) -> String {
+    if href.starts_with("http://") || href.starts_with("https://") {
+        href.to_string()
+    } else if let Some(rest) = href.strip_prefix("//") {
+        format!("https://{}", rest)
+    } else {
+        format!("{}/{}", base_url, href.trim_start_matches('/'))
+    }
+}
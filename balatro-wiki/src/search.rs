@@ -0,0 +1,332 @@
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+use std::str::FromStr;
+
+use crate::database::{ModDatabase, ModInfo};
+
+/// A mod field that can contribute to a search score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Name,
+    Description,
+    Author,
+    Category,
+}
+
+impl SearchField {
+    pub const ALL: [SearchField; 4] = [
+        SearchField::Name,
+        SearchField::Description,
+        SearchField::Author,
+        SearchField::Category,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchField::Name => "name",
+            SearchField::Description => "description",
+            SearchField::Author => "author",
+            SearchField::Category => "category",
+        }
+    }
+}
+
+impl FromStr for SearchField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        SearchField::ALL.into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| anyhow!("Unknown search field '{}' (expected name, description, author, or category)", s))
+    }
+}
+
+/// Options that tune a [`search`].
+#[derive(Debug, Clone, Default)]
+pub struct SearchOpts {
+    /// Keep only the best `limit` matches.
+    pub limit: Option<usize>,
+    /// Treat the query as a regular expression instead of a substring.
+    pub regex: bool,
+    /// Match the regular expression case-insensitively (substring search
+    /// always ignores case).
+    pub ignore_case: bool,
+    /// Only consider mods listed under this category (case-insensitive).
+    /// Aliases must already be resolved, see [`Categories::canonical`](crate::Categories::canonical).
+    pub category: Option<String>,
+    /// Only consider mods whose author contains this text (case-insensitive).
+    /// Mods with no known author are skipped.
+    pub author: Option<String>,
+    /// Only consider mods with this infobox tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Fields that contribute to the score. Empty means all of them.
+    pub fields: Vec<SearchField>,
+    /// Require every whitespace-separated word of a substring query to match
+    /// (by default a mod matching any word is included).
+    pub all_words: bool,
+    /// Drop matches scoring below this (see [`calculate_search_score`] for the tiers).
+    pub min_score: i32,
+}
+
+impl SearchOpts {
+    /// Returns true if `mod_info` passes the category, author, and tag filters.
+    pub fn accepts(&self, mod_info: &ModInfo) -> bool {
+        if let Some(category) = &self.category {
+            if !mod_info.in_category(category) {
+                return false;
+            }
+        }
+        if let Some(author) = &self.author {
+            match &mod_info.author {
+                Some(mod_author) if mod_author.to_lowercase().contains(&author.to_lowercase()) => {}
+                _ => return false,
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !mod_info.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Ranks every mod in `db` against `query` and returns the matches sorted by
+/// descending score, with equal scores in name order. Fails only if
+/// `opts.regex` is set and the query is not a valid regular expression.
+pub fn search(db: &ModDatabase, query: &str, opts: SearchOpts) -> Result<Vec<(ModInfo, i32)>> {
+    let pattern = if opts.regex {
+        let pattern = RegexBuilder::new(query)
+            .case_insensitive(opts.ignore_case)
+            .build()
+            .map_err(|e| anyhow!("Invalid regex '{}': {}", query, e))?;
+        Some(pattern)
+    } else {
+        None
+    };
+    
+    let fields: &[SearchField] = if opts.fields.is_empty() { &SearchField::ALL } else { &opts.fields };
+    let query_lower = query.to_lowercase();
+    let word_count = query_lower.split_whitespace().count();
+    let index = db.search_index();
+    let mut matches: Vec<(ModInfo, i32)> = db.mods.values()
+        .filter(|mod_info| opts.accepts(mod_info))
+        .map(|mod_info| {
+            let score = match &pattern {
+                Some(pattern) => calculate_regex_score(mod_info, pattern, fields),
+                None => {
+                    let built;
+                    let text = match index.get(&mod_info.name) {
+                        Some(text) => text,
+                        None => {
+                            built = SearchText::new(mod_info);
+                            &built
+                        }
+                    };
+                    if opts.all_words && text_matching_words(text, &query_lower, fields) < word_count {
+                        0
+                    } else {
+                        text_score(text, &query_lower, fields)
+                    }
+                }
+            };
+            (mod_info, score)
+        })
+        .filter(|(_, score)| *score > 0 && *score >= opts.min_score)
+        .map(|(mod_info, score)| (mod_info.clone(), score))
+        .collect();
+    
+    matches.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+    
+    if let Some(limit) = opts.limit {
+        matches.truncate(limit);
+    }
+    
+    Ok(matches)
+}
+
+/// Lowercased copies of the mod fields a substring search matches against,
+/// so repeated searches don't lowercase every mod again.
+#[derive(Debug, Clone)]
+pub(crate) struct SearchText {
+    name: String,
+    description: String,
+    author: Option<String>,
+    categories: Vec<String>,
+}
+
+impl SearchText {
+    pub(crate) fn new(mod_info: &ModInfo) -> Self {
+        Self {
+            name: mod_info.name.to_lowercase(),
+            description: mod_info.description.to_lowercase(),
+            author: mod_info.author.as_ref().map(|a| a.to_lowercase()),
+            categories: mod_info.categories.iter().map(|c| c.to_lowercase()).collect(),
+        }
+    }
+}
+
+/// Scores how well a mod matches a lowercase query. Each whitespace-separated
+/// word is scored on its own and the scores are summed: 100 for an exact name
+/// match, 50 for a name substring, plus 25 for description, 20 for author,
+/// and 15 for category matches. A query of several words also earns a bonus
+/// of 100 if it is the whole name, or 50 if the name contains it as a phrase,
+/// so "joker pack" ranks "Joker Pack" above "Joker Pack Plus". Zero means no
+/// word matched.
+pub fn calculate_search_score(mod_info: &ModInfo, query: &str) -> i32 {
+    calculate_search_score_in(mod_info, query, &SearchField::ALL)
+}
+
+/// Like [`calculate_search_score`], but only `fields` contribute to the score.
+pub fn calculate_search_score_in(mod_info: &ModInfo, query: &str, fields: &[SearchField]) -> i32 {
+    text_score(&SearchText::new(mod_info), query, fields)
+}
+
+/// Counts the words of a lowercase query that match at least one of `fields`.
+pub fn matching_words(mod_info: &ModInfo, query: &str, fields: &[SearchField]) -> usize {
+    text_matching_words(&SearchText::new(mod_info), query, fields)
+}
+
+fn text_score(text: &SearchText, query: &str, fields: &[SearchField]) -> i32 {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let score: i32 = words.iter().map(|word| word_score(text, word, fields)).sum();
+    
+    // Single words already got the name bonus above
+    if words.len() < 2 || !fields.contains(&SearchField::Name) {
+        return score;
+    }
+    let phrase = words.join(" ");
+    if text.name == phrase {
+        score + 100
+    } else if text.name.contains(&phrase) {
+        score + 50
+    } else {
+        score
+    }
+}
+
+fn text_matching_words(text: &SearchText, query: &str, fields: &[SearchField]) -> usize {
+    query.split_whitespace().filter(|word| word_score(text, word, fields) > 0).count()
+}
+
+fn word_score(text: &SearchText, word: &str, fields: &[SearchField]) -> i32 {
+    let mut score = 0;
+    
+    // Exact name match gets highest score
+    if fields.contains(&SearchField::Name) {
+        if text.name == word {
+            score += 100;
+        } else if text.name.contains(word) {
+            score += 50;
+        }
+    }
+    
+    // Description match
+    if fields.contains(&SearchField::Description) && text.description.contains(word) {
+        score += 25;
+    }
+    
+    // Author match
+    if fields.contains(&SearchField::Author) {
+        if let Some(author) = &text.author {
+            if author.contains(word) {
+                score += 20;
+            }
+        }
+    }
+    
+    // Category match
+    if fields.contains(&SearchField::Category) && text.categories.iter().any(|c| c.contains(word)) {
+        score += 15;
+    }
+    
+    score
+}
+
+/// Scores a mod against a regular expression: 100 if it matches the whole
+/// name, 50 if it matches within the name, plus 25 for a description, 20 for
+/// an author, and 15 for a category match, as substring scoring does for a
+/// word. Only `fields` are considered.
+pub fn calculate_regex_score(mod_info: &ModInfo, pattern: &Regex, fields: &[SearchField]) -> i32 {
+    let mut score = 0;
+    
+    if fields.contains(&SearchField::Name) {
+        if let Some(m) = pattern.find(&mod_info.name) {
+            score += if m.len() == mod_info.name.len() { 100 } else { 50 };
+        }
+    }
+    
+    if fields.contains(&SearchField::Description) && pattern.is_match(&mod_info.description) {
+        score += 25;
+    }
+    
+    if fields.contains(&SearchField::Author) && mod_info.author.as_deref().is_some_and(|a| pattern.is_match(a)) {
+        score += 20;
+    }
+    
+    if fields.contains(&SearchField::Category) && mod_info.categories.iter().any(|c| pattern.is_match(c)) {
+        score += 15;
+    }
+    
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_scores_are_ordered_by_name() {
+        let mut db = ModDatabase::new();
+        for name in ["Zeta Pack", "Alpha Pack", "Mid Pack", "Pack"] {
+            db.mods.insert(name.to_string(), ModInfo::named(name));
+        }
+        
+        let matches = search(&db, "pack", SearchOpts::default()).unwrap();
+        let names: Vec<(&str, i32)> = matches.iter().map(|(m, score)| (m.name.as_str(), *score)).collect();
+        assert_eq!(names, vec![("Pack", 100), ("Alpha Pack", 50), ("Mid Pack", 50), ("Zeta Pack", 50)]);
+    }
+
+    #[test]
+    fn clearing_the_index_picks_up_changed_mods() {
+        let mut db = ModDatabase::new();
+        db.mods.insert("Cryptid".to_string(), ModInfo::named("Cryptid"));
+        assert_eq!(search(&db, "jokers", SearchOpts::default()).unwrap().len(), 0);
+        
+        db.mods.get_mut("Cryptid").unwrap().description = "Adds new Jokers".to_string();
+        db.clear_search_index();
+        assert_eq!(search(&db, "jokers", SearchOpts::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn whole_name_and_phrase_matches_outrank_scattered_words() {
+        let mut db = ModDatabase::new();
+        for name in ["Joker Pack Plus", "Pack of Jokers", "Joker Pack", "Big Joker Pack"] {
+            db.mods.insert(name.to_string(), ModInfo::named(name));
+        }
+        
+        let matches = search(&db, "Joker  Pack", SearchOpts::default()).unwrap();
+        let names: Vec<(&str, i32)> = matches.iter().map(|(m, score)| (m.name.as_str(), *score)).collect();
+        assert_eq!(
+            names,
+            vec![("Joker Pack", 200), ("Big Joker Pack", 150), ("Joker Pack Plus", 150), ("Pack of Jokers", 100)]
+        );
+    }
+
+    #[test]
+    fn regex_scores_author_and_category_matches() {
+        let cryptid = ModInfo {
+            author: Some("MathIsFun".to_string()),
+            categories: vec!["Content Mods".to_string()],
+            ..ModInfo::named("Cryptid")
+        };
+        let score = |pattern: &str, fields: &[SearchField]| {
+            calculate_regex_score(&cryptid, &Regex::new(pattern).unwrap(), fields)
+        };
+        
+        assert_eq!(score("^Math", &SearchField::ALL), 20);
+        assert_eq!(score("Content", &SearchField::ALL), 15);
+        assert_eq!(score("^Cryptid$|Math|Content", &SearchField::ALL), 135);
+        assert_eq!(score("Math|Content", &[SearchField::Name, SearchField::Description]), 0);
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::{anyhow, Result};
+use scraper::Selector;
+use std::sync::OnceLock;
+
+/// A CSS selector compiled the first time it is used and shared by every
+/// page parsed afterwards. Declared as a `static` next to the code using it.
+pub(crate) struct LazySelector {
+    css: &'static str,
+    compiled: OnceLock<Option<Selector>>,
+}
+
+impl LazySelector {
+    pub(crate) const fn new(css: &'static str) -> Self {
+        Self { css, compiled: OnceLock::new() }
+    }
+
+    /// Returns the compiled selector, or an error naming the CSS if it
+    /// doesn't parse.
+    pub(crate) fn get(&self) -> Result<&Selector> {
+        self.compiled
+            .get_or_init(|| Selector::parse(self.css).ok())
+            .as_ref()
+            .ok_or_else(|| anyhow!("Invalid CSS selector '{}'", self.css))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_selector_is_an_error_not_a_panic() {
+        static GOOD: LazySelector = LazySelector::new(".infobox tr");
+        static BAD: LazySelector = LazySelector::new(".infobox[");
+
+        assert!(GOOD.get().is_ok());
+        assert!(std::ptr::eq(GOOD.get().unwrap(), GOOD.get().unwrap()));
+        assert!(BAD.get().unwrap_err().to_string().contains(".infobox["));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::database::ModDatabase;
+
+/// Renders a sitemaps.org XML sitemap listing every mod's wiki page, with
+/// `<lastmod>` taken from the mod's `last_updated` when known.
+pub fn sitemap_xml(db: &ModDatabase) -> String {
+    let mut mods: Vec<_> = db.mods.values().collect();
+    mods.sort_by(|a, b| a.wiki_url.cmp(&b.wiki_url));
+    
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for mod_info in mods {
+        let url = match mod_info.wiki_url.strip_prefix("http://") {
+            Some(rest) => format!("https://{}", rest),
+            None => mod_info.wiki_url.clone(),
+        };
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&url)));
+        if let Some(last_updated) = &mod_info.last_updated {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", escape_xml(last_updated)));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::bookmarks::Bookmarks;
+use crate::database::{migrate, ModDatabase};
+use crate::installed::{InstalledMod, InstalledMods};
+
+/// Format version written into new snapshots. Snapshots with a newer
+/// version are refused rather than half-read.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A portable copy of a cache: the mod database, plus the bookmarks,
+/// favorites, and installed-mod manifest beside it if they exist, in one
+/// JSON file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    /// When the snapshot was taken (RFC 3339).
+    pub created_at: String,
+    pub database: ModDatabase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bookmarks: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favorites: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed: Option<Vec<InstalledMod>>,
+}
+
+impl Snapshot {
+    /// Takes a snapshot of the cache at `cache_path` and the files beside it.
+    pub fn capture(cache_path: &Path) -> Result<Self> {
+        let installed_path = InstalledMods::path_for(cache_path);
+        let names_in = |path: PathBuf| {
+            path.exists().then(|| Bookmarks::load(&path).map(|b| b.iter().cloned().collect())).transpose()
+        };
+        let installed = installed_path.exists()
+            .then(|| InstalledMods::load(&installed_path).map(|m| m.iter().cloned().collect()))
+            .transpose()?;
+        Ok(Self {
+            version: SNAPSHOT_VERSION,
+            created_at: Utc::now().to_rfc3339(),
+            database: ModDatabase::load_from(cache_path)?,
+            bookmarks: names_in(Bookmarks::path_for(cache_path))?,
+            favorites: names_in(Bookmarks::favorites_path_for(cache_path))?,
+            installed,
+        })
+    }
+
+    /// Writes the snapshot to `path` as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Reads the snapshot at `path`, checking its format version before
+    /// parsing the rest, and upgrading its database if that was written in
+    /// an older format.
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read snapshot {}", path.display()))?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Snapshot {} is not valid JSON", path.display()))?;
+        match value.get("version").and_then(|v| v.as_u64()) {
+            Some(version) if (1..=SNAPSHOT_VERSION as u64).contains(&version) => {}
+            Some(version) => {
+                return Err(anyhow!(
+                    "Snapshot {} has format version {}, but this build only reads up to version {}",
+                    path.display(), version, SNAPSHOT_VERSION
+                ));
+            }
+            None => return Err(anyhow!("{} is not a balatro-wiki snapshot (no format version)", path.display())),
+        }
+        if let Some(database) = value.get_mut("database") {
+            *database = migrate(database.take())
+                .with_context(|| format!("Snapshot {} holds an unusable database", path.display()))?;
+        }
+        serde_json::from_value(value).with_context(|| format!("Invalid snapshot {}", path.display()))
+    }
+
+    /// Returns where [`Snapshot::restore`] backs up the cache at `cache_path`.
+    pub fn backup_path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name(format!("backup-{}.json", Utc::now().format("%Y%m%dT%H%M%S")))
+    }
+
+    /// Replaces the cache at `cache_path` with the snapshot's database, and
+    /// the bookmarks, favorites, and installed manifest with the snapshot's,
+    /// if it has them. Whatever was there is first saved as a snapshot of its own,
+    /// whose path is returned; nothing is backed up if there was no cache.
+    /// The restored categories count as just scraped, so reading them doesn't
+    /// refresh them from the wiki and replace what was restored; the
+    /// database's `last_updated`, which `update --since-last-run` starts
+    /// from, is kept.
+    pub fn restore(&self, cache_path: &Path) -> Result<Option<PathBuf>> {
+        let existing = [
+            cache_path.to_path_buf(),
+            Bookmarks::path_for(cache_path),
+            Bookmarks::favorites_path_for(cache_path),
+            InstalledMods::path_for(cache_path),
+        ];
+        let backup = if existing.iter().any(|path| path.exists()) {
+            let backup = Self::backup_path_for(cache_path);
+            Self::capture(cache_path)?.write(&backup)
+                .with_context(|| format!("Could not back up the current cache to {}", backup.display()))?;
+            Some(backup)
+        } else {
+            None
+        };
+        
+        let mut database = self.database.clone();
+        database.clear_search_index();
+        let now = Utc::now().to_rfc3339();
+        for category in database.categories.keys() {
+            database.category_updated.insert(category.clone(), now.clone());
+        }
+        database.save_to(cache_path)?;
+        let lists = [
+            (&self.bookmarks, Bookmarks::path_for(cache_path)),
+            (&self.favorites, Bookmarks::favorites_path_for(cache_path)),
+        ];
+        for (names, path) in lists {
+            if let Some(names) = names {
+                let mut bookmarks = Bookmarks::default();
+                for name in names {
+                    bookmarks.insert(name);
+                }
+                bookmarks.save(&path)?;
+            }
+        }
+        if let Some(mods) = &self.installed {
+            let mut installed = InstalledMods::default();
+            for installed_mod in mods {
+                installed.record(installed_mod.clone());
+            }
+            installed.save(&InstalledMods::path_for(cache_path))?;
+        }
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::DEFAULT_MAX_AGE;
+
+    #[test]
+    fn restore_backs_up_the_cache_and_refuses_newer_snapshots() {
+        let dir = std::env::temp_dir().join(format!("balatro-wiki-snapshot-{}", std::process::id()));
+        let source = dir.join("source").join("mods.json");
+        let target = dir.join("target").join("mods.json");
+        
+        let mut db = ModDatabase::new();
+        db.categories.insert("Joker Mods".to_string(), Vec::new());
+        db.save_to(&source).unwrap();
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.insert("Cryptid");
+        bookmarks.save(&Bookmarks::path_for(&source)).unwrap();
+        let snapshot_path = dir.join("shared.json");
+        Snapshot::capture(&source).unwrap().write(&snapshot_path).unwrap();
+        
+        ModDatabase::new().save_to(&target).unwrap();
+        let snapshot = Snapshot::read(&snapshot_path).unwrap();
+        assert!(snapshot.installed.is_none());
+        let backup = snapshot.restore(&target).unwrap().expect("the existing cache is backed up");
+        let restored = ModDatabase::load_from(&target).unwrap();
+        let restored_bookmarks = Bookmarks::load(&Bookmarks::path_for(&target)).unwrap();
+        let backed_up = Snapshot::read(&backup).unwrap();
+        
+        let content = std::fs::read_to_string(&snapshot_path).unwrap();
+        let mut newer: serde_json::Value = serde_json::from_str(&content).unwrap();
+        newer["version"] = (SNAPSHOT_VERSION + 1).into();
+        std::fs::write(&snapshot_path, newer.to_string()).unwrap();
+        let refused = Snapshot::read(&snapshot_path);
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert!(restored.categories.contains_key("Joker Mods"));
+        assert!(restored.stale_categories(DEFAULT_MAX_AGE).is_empty());
+        assert_eq!(restored.last_updated, snapshot.database.last_updated);
+        assert!(restored_bookmarks.contains("cryptid"));
+        assert!(backed_up.database.categories.is_empty());
+        assert!(backed_up.bookmarks.is_none());
+        assert!(refused.is_err());
+    }
+}
@@ -0,0 +1,467 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Params};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::database::{migrate, ModDatabase, ModInfo};
+use crate::text::normalize_name;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS mods (
+        name TEXT PRIMARY KEY,
+        name_lower TEXT NOT NULL,
+        info TEXT NOT NULL,
+        name_key TEXT NOT NULL DEFAULT '',
+        search_text TEXT NOT NULL DEFAULT ''
+    );
+    CREATE INDEX IF NOT EXISTS mods_by_lower_name ON mods (name_lower);
+    CREATE TABLE IF NOT EXISTS categories (
+        category TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        mod_name TEXT NOT NULL,
+        PRIMARY KEY (category, position)
+    );
+    CREATE TABLE IF NOT EXISTS category_updated (
+        category TEXT PRIMARY KEY,
+        updated TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS dependencies (
+        mod_name TEXT NOT NULL,
+        dependency TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS dependencies_by_dependency ON dependencies (dependency);
+";
+
+/// The mod database kept in SQLite, in `mods.sqlite` next to the JSON
+/// cache. Mods are stored as JSON rows keyed by name, with category
+/// membership and dependencies in their own indexed tables, so the file
+/// can also be queried with plain SQL. Besides whole-database loads and
+/// saves, a mod can be looked up by name, a search can load just the mods
+/// that contain a query word, and an update writes only what changed.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Returns the SQLite file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("mods.sqlite")
+    }
+
+    /// Opens the store at `path`, creating the file and tables if needed.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Opens an empty store that lives only in memory.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        let mut store = Self { conn };
+        store.add_lookup_columns()?;
+        Ok(store)
+    }
+
+    /// Adds the normalized-name and search-text columns to a store written
+    /// before they existed, filling them from each mod's stored JSON.
+    fn add_lookup_columns(&mut self) -> Result<()> {
+        if self.conn.prepare("SELECT name_key, search_text FROM mods LIMIT 0").is_err() {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(
+                "ALTER TABLE mods ADD COLUMN name_key TEXT NOT NULL DEFAULT '';
+                 ALTER TABLE mods ADD COLUMN search_text TEXT NOT NULL DEFAULT '';",
+            )?;
+            let mods: Vec<ModInfo> = {
+                let mut rows = tx.prepare("SELECT info FROM mods")?;
+                let infos = rows.query_map([], |row| row.get::<_, String>(0))?;
+                infos.map(|info| Ok(serde_json::from_str(&info?)?)).collect::<Result<_>>()?
+            };
+            for mod_info in &mods {
+                tx.execute(
+                    "UPDATE mods SET name_key = ?1, search_text = ?2 WHERE name = ?3",
+                    params![normalize_name(&mod_info.name), search_text(mod_info), mod_info.name],
+                )?;
+            }
+            tx.commit()?;
+        }
+        self.conn.execute("CREATE INDEX IF NOT EXISTS mods_by_name_key ON mods (name_key)", [])?;
+        Ok(())
+    }
+
+    /// Reads the whole database, upgrading it first if it was saved in an
+    /// older format (see [`SCHEMA_VERSION`](crate::SCHEMA_VERSION)). An empty
+    /// store gives an empty database.
+    pub fn load(&self) -> Result<ModDatabase> {
+        self.load_where("1", [])
+    }
+
+    /// Reads the database with the mod named `name` as its only mod, found
+    /// the way [`ModDatabase::find_normalized`] would: ignoring case, else
+    /// by normalized name. Categories and timestamps are read in full.
+    pub fn load_named(&self, name: &str) -> Result<ModDatabase> {
+        let db = self.load_where("name_lower = ?1", params![name.to_lowercase()])?;
+        let key = normalize_name(name);
+        if !db.mods.is_empty() || key.is_empty() {
+            return Ok(db);
+        }
+        // Several mods sharing the key load together, and find_normalized then matches none of them
+        self.load_where("name_key = ?1", params![key])
+    }
+
+    /// Reads the database with only the mods that contain a word of `query`
+    /// in their name, description, author, or categories, ignoring case:
+    /// every mod a substring [`search`](crate::search) for it can match.
+    /// Every other mod is present by name only, so suggestions for a query
+    /// with no matches still cover the whole cache.
+    pub fn load_matching(&self, query: &str) -> Result<ModDatabase> {
+        let words: Vec<String> = query.to_lowercase().split_whitespace().map(str::to_string).collect();
+        let condition = if words.is_empty() {
+            "0".to_string()
+        } else {
+            vec!["instr(search_text, ?) > 0"; words.len()].join(" OR ")
+        };
+        let mut db = self.load_where(&condition, params_from_iter(&words))?;
+        for name in self.mod_names()? {
+            db.mods.entry(name.clone()).or_insert_with(|| ModInfo::named(&name));
+        }
+        Ok(db)
+    }
+
+    /// Reads the database with every mod present by name only, without
+    /// decoding any stored mod: enough to count mods, in all or in one
+    /// category, and to tell whether the cache is stale.
+    pub fn load_names(&self) -> Result<ModDatabase> {
+        let mut db = self.load_where("0", [])?;
+        for name in self.mod_names()? {
+            db.mods.insert(name.clone(), ModInfo::named(&name));
+        }
+        Ok(db)
+    }
+
+    fn mod_names(&self) -> Result<Vec<String>> {
+        let mut rows = self.conn.prepare("SELECT name FROM mods")?;
+        let names = rows.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+        Ok(names)
+    }
+
+    /// Reads the categories and timestamps in full, and the mods matching the
+    /// SQL `condition`, upgrading the result if the store is in an older
+    /// format.
+    fn load_where(&self, condition: &str, params: impl Params) -> Result<ModDatabase> {
+        // Stores from before the version was recorded are version 1
+        let schema_version: u32 = match self.meta("schema_version")? {
+            Some(version) => version.parse().context("Invalid schema version in mods.sqlite")?,
+            None => 1,
+        };
+        let last_updated = match self.meta("last_updated")? {
+            Some(last_updated) => last_updated,
+            None => ModDatabase::new().last_updated,
+        };
+        
+        let mut mods = serde_json::Map::new();
+        let mut rows = self.conn.prepare(&format!("SELECT name, info FROM mods WHERE {}", condition))?;
+        for row in rows.query_map(params, |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+            let (name, info) = row?;
+            mods.insert(name, serde_json::from_str(&info)?);
+        }
+        
+        let mut categories: HashMap<String, Vec<String>> = HashMap::new();
+        let mut members = self.conn.prepare("SELECT category, mod_name FROM categories ORDER BY category, position")?;
+        for row in members.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+            let (category, mod_name) = row?;
+            categories.entry(category).or_default().push(mod_name);
+        }
+        
+        let mut category_updated = HashMap::new();
+        let mut updated = self.conn.prepare("SELECT category, updated FROM category_updated")?;
+        for row in updated.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+            let (category, updated) = row?;
+            category_updated.insert(category, updated);
+        }
+        
+        // Migrations work on the JSON cache format, so the store is read as one
+        let value = json!({
+            "schema_version": schema_version,
+            "mods": mods,
+            "categories": categories,
+            "last_updated": last_updated,
+            "category_updated": category_updated,
+        });
+        let value = migrate(value).context("mods.sqlite can't be read")?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Replaces everything in the store with `db`, in one transaction.
+    pub fn save(&mut self, db: &ModDatabase) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute_batch(
+            "DELETE FROM mods; DELETE FROM categories; DELETE FROM category_updated; DELETE FROM dependencies;",
+        )?;
+        write_meta(&tx, db)?;
+        for mod_info in db.mods.values() {
+            insert_mod(&tx, mod_info)?;
+        }
+        for (category, mod_names) in &db.categories {
+            insert_members(&tx, category, mod_names)?;
+        }
+        for (category, updated) in &db.category_updated {
+            tx.execute("INSERT INTO category_updated (category, updated) VALUES (?1, ?2)", params![category, updated])?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Writes `db` over `previous`, the database the store currently holds,
+    /// in one transaction: only mods that were added, changed, or removed,
+    /// and categories whose members or timestamp changed, are rewritten. An
+    /// update of one category touches just that category's rows.
+    pub fn save_changes(&mut self, previous: &ModDatabase, db: &ModDatabase) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        write_meta(&tx, db)?;
+        for name in previous.mods.keys().filter(|name| !db.mods.contains_key(*name)) {
+            delete_mod(&tx, name)?;
+        }
+        for (name, mod_info) in db.mods.iter().filter(|(name, mod_info)| previous.mods.get(*name) != Some(*mod_info)) {
+            delete_mod(&tx, name)?;
+            insert_mod(&tx, mod_info)?;
+        }
+        for (category, mod_names) in &previous.categories {
+            if db.categories.get(category) != Some(mod_names) {
+                tx.execute("DELETE FROM categories WHERE category = ?1", params![category])?;
+            }
+        }
+        for (category, mod_names) in &db.categories {
+            if previous.categories.get(category) != Some(mod_names) {
+                insert_members(&tx, category, mod_names)?;
+            }
+        }
+        let dropped = previous.category_updated.keys().filter(|category| !db.category_updated.contains_key(*category));
+        for category in dropped {
+            tx.execute("DELETE FROM category_updated WHERE category = ?1", params![category])?;
+        }
+        for (category, updated) in &db.category_updated {
+            if previous.category_updated.get(category) != Some(updated) {
+                tx.execute(
+                    "INSERT OR REPLACE INTO category_updated (category, updated) VALUES (?1, ?2)",
+                    params![category, updated],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.conn.query_row("SELECT value FROM meta WHERE key = ?1", params![key], |row| row.get(0)).optional()?)
+    }
+}
+
+fn write_meta(conn: &Connection, db: &ModDatabase) -> Result<()> {
+    conn.execute("INSERT OR REPLACE INTO meta (key, value) VALUES ('last_updated', ?1)", params![db.last_updated])?;
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('schema_version', ?1)",
+        params![db.schema_version.to_string()],
+    )?;
+    Ok(())
+}
+
+fn insert_mod(conn: &Connection, mod_info: &ModInfo) -> Result<()> {
+    conn.execute(
+        "INSERT INTO mods (name, name_lower, info, name_key, search_text) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            mod_info.name,
+            mod_info.name.to_lowercase(),
+            serde_json::to_string(mod_info)?,
+            normalize_name(&mod_info.name),
+            search_text(mod_info),
+        ],
+    )?;
+    for dependency in &mod_info.dependencies {
+        conn.execute(
+            "INSERT INTO dependencies (mod_name, dependency) VALUES (?1, ?2)",
+            params![mod_info.name, dependency],
+        )?;
+    }
+    Ok(())
+}
+
+fn delete_mod(conn: &Connection, name: &str) -> Result<()> {
+    conn.execute("DELETE FROM mods WHERE name = ?1", params![name])?;
+    conn.execute("DELETE FROM dependencies WHERE mod_name = ?1", params![name])?;
+    Ok(())
+}
+
+fn insert_members(conn: &Connection, category: &str, mod_names: &[String]) -> Result<()> {
+    for (position, mod_name) in mod_names.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO categories (category, position, mod_name) VALUES (?1, ?2, ?3)",
+            params![category, position as i64, mod_name],
+        )?;
+    }
+    Ok(())
+}
+
+/// The lowercased fields a substring search matches, one per line. Query
+/// words contain no whitespace, so a word found here was found in one field.
+fn search_text(mod_info: &ModInfo) -> String {
+    let mut fields = vec![mod_info.name.as_str(), mod_info.description.as_str()];
+    fields.extend(mod_info.author.as_deref());
+    fields.extend(mod_info.categories.iter().map(String::as_str));
+    fields.join("\n").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SCHEMA_VERSION;
+
+    fn mod_named(name: &str, dependencies: &[&str]) -> ModInfo {
+        ModInfo {
+            description: format!("{} description", name),
+            categories: vec!["Content Mods".to_string()],
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            ..ModInfo::named(name)
+        }
+    }
+
+    #[test]
+    fn database_round_trips() {
+        let mut db = ModDatabase::new();
+        for mod_info in [mod_named("Cryptid", &["Talisman", "Steamodded"]), mod_named("Talisman", &["Steamodded"])] {
+            db.mods.insert(mod_info.name.clone(), mod_info);
+        }
+        db.categories.insert("Content Mods".to_string(), vec!["Talisman".to_string(), "Cryptid".to_string()]);
+        db.category_updated.insert("Content Mods".to_string(), "2024-03-01T00:00:00+00:00".to_string());
+        
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        store.save(&db).unwrap();
+        let loaded = store.load().unwrap();
+        
+        assert_eq!(loaded.last_updated, db.last_updated);
+        assert_eq!(loaded.categories, db.categories);
+        assert_eq!(loaded.category_updated, db.category_updated);
+        assert_eq!(loaded.mods["Cryptid"].dependencies, ["Talisman", "Steamodded"]);
+        
+        db.mods.remove("Cryptid");
+        store.save(&db).unwrap();
+        assert_eq!(store.load().unwrap().mods.len(), 1);
+    }
+
+    #[test]
+    fn stores_without_a_version_load_and_newer_ones_are_refused() {
+        let mut db = ModDatabase::new();
+        db.mods.insert("Cryptid".to_string(), mod_named("Cryptid", &[]));
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        store.save(&db).unwrap();
+        
+        store.conn.execute("DELETE FROM meta WHERE key = 'schema_version'", []).unwrap();
+        let legacy = store.load().unwrap();
+        store.conn
+            .execute("INSERT INTO meta (key, value) VALUES ('schema_version', ?1)", params![SCHEMA_VERSION + 1])
+            .unwrap();
+        
+        assert_eq!(legacy.schema_version, SCHEMA_VERSION);
+        assert!(legacy.mods.contains_key("Cryptid"));
+        assert!(store.load().is_err());
+    }
+
+    #[test]
+    fn lookups_load_only_the_mods_they_need() {
+        let mut db = ModDatabase::new();
+        for mod_info in [
+            mod_named("Cryptid", &[]),
+            mod_named("Talisman", &[]),
+            ModInfo { author: Some("MathIsFun".to_string()), ..mod_named("Jen's Almanac", &[]) },
+        ] {
+            db.mods.insert(mod_info.name.clone(), mod_info);
+        }
+        db.categories.insert("Content Mods".to_string(), vec!["Cryptid".to_string(), "Talisman".to_string()]);
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        store.save(&db).unwrap();
+        
+        let named = store.load_named("cryptid").unwrap();
+        assert_eq!(named.mods.keys().collect::<Vec<_>>(), ["Cryptid"]);
+        assert_eq!(named.categories, db.categories);
+        let normalized = store.load_named("Jens Almanac").unwrap();
+        assert!(normalized.find_normalized("Jens Almanac").is_some());
+        assert!(store.load_named("Steamodded").unwrap().mods.is_empty());
+        
+        let matching = store.load_matching("mathisfun TALIS").unwrap();
+        assert_eq!(matching.mods.len(), 3);
+        assert!(matching.mods["Jen's Almanac"].author.is_some());
+        assert_eq!(matching.mods["Talisman"].description, "Talisman description");
+        assert_eq!(matching.mods["Cryptid"], ModInfo::named("Cryptid"));
+        
+        let names = store.load_names().unwrap();
+        assert_eq!(names.mods.len(), 3);
+        assert_eq!(names.mods["Talisman"], ModInfo::named("Talisman"));
+        assert_eq!(names.categories, db.categories);
+    }
+
+    #[test]
+    fn saving_changes_rewrites_only_what_changed() {
+        let mut previous = ModDatabase::new();
+        for mod_info in [mod_named("Cryptid", &["Talisman"]), mod_named("Talisman", &[]), mod_named("Bunco", &[])] {
+            previous.mods.insert(mod_info.name.clone(), mod_info);
+        }
+        previous.categories.insert("Content Mods".to_string(), vec!["Cryptid".to_string(), "Bunco".to_string()]);
+        previous.categories.insert("API Mods".to_string(), vec!["Talisman".to_string()]);
+        previous.category_updated.insert("Content Mods".to_string(), "2024-03-01T00:00:00+00:00".to_string());
+        previous.category_updated.insert("API Mods".to_string(), "2024-03-01T00:00:00+00:00".to_string());
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        store.save(&previous).unwrap();
+        
+        let mut db = previous.clone();
+        db.mods.remove("Bunco");
+        db.mods.get_mut("Cryptid").unwrap().dependencies.clear();
+        db.categories.insert("Content Mods".to_string(), vec!["Cryptid".to_string()]);
+        db.category_updated.insert("Content Mods".to_string(), "2024-04-01T00:00:00+00:00".to_string());
+        // Marking an unchanged row shows whether it gets rewritten
+        store.conn.execute("UPDATE mods SET search_text = 'untouched' WHERE name = 'Talisman'", []).unwrap();
+        store.save_changes(&previous, &db).unwrap();
+        let loaded = store.load().unwrap();
+        
+        assert_eq!(loaded.mods, db.mods);
+        assert_eq!(loaded.categories, db.categories);
+        assert_eq!(loaded.category_updated, db.category_updated);
+        let dependencies: i64 =
+            store.conn.query_row("SELECT COUNT(*) FROM dependencies", [], |row| row.get(0)).unwrap();
+        assert_eq!(dependencies, 0);
+        let talisman: String = store.conn
+            .query_row("SELECT search_text FROM mods WHERE name = 'Talisman'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(talisman, "untouched");
+    }
+
+    #[test]
+    fn stores_from_before_the_lookup_columns_are_upgraded() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE mods (name TEXT PRIMARY KEY, name_lower TEXT NOT NULL, info TEXT NOT NULL);",
+        )
+        .unwrap();
+        let info = serde_json::to_string(&mod_named("Jen's Almanac", &[])).unwrap();
+        conn.execute(
+            "INSERT INTO mods (name, name_lower, info) VALUES ('Jen''s Almanac', 'jen''s almanac', ?1)",
+            params![info],
+        )
+        .unwrap();
+        
+        let store = SqliteStore::from_connection(conn).unwrap();
+        
+        assert_eq!(store.load_named("jens almanac").unwrap().mods.len(), 1);
+        let matching = store.load_matching("almanac").unwrap();
+        assert_eq!(matching.mods["Jen's Almanac"].description, "Jen's Almanac description");
+    }
+}
@@ -0,0 +1,326 @@
+use anyhow::Result;
+use regex::Regex;
+use scraper::Html;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use crate::selectors::LazySelector;
+
+static LEAD_PARAGRAPHS: LazySelector = LazySelector::new("div.mw-parser-output > p");
+static INFOBOX_ROWS: LazySelector = LazySelector::new(".infobox tr");
+static DATA_CELLS: LazySelector = LazySelector::new("td");
+static CONTENT_BLOCKS: LazySelector = LazySelector::new("div.mw-parser-output div, div.mw-parser-output li");
+static LIST_ITEMS: LazySelector = LazySelector::new("div.mw-parser-output ul li");
+
+/// Builds a mod description from a parsed wiki page: the first substantive
+/// lead paragraph, or else the infobox description, the feature list, or any
+/// other block of text, in that order.
+pub fn extract_description(document: &Html) -> Result<String> {
+    let lead = document.select(LEAD_PARAGRAPHS.get()?)
+        .map(|para| clean_text(&para.text().collect::<Vec<_>>().join(" ")))
+        .find(|cleaned| is_substantive_paragraph(cleaned));
+    if let Some(lead) = lead {
+        return Ok(truncate(&lead, 500));
+    }
+    
+    // Infobox description
+    let cell_selector = DATA_CELLS.get()?;
+    for row in document.select(INFOBOX_ROWS.get()?) {
+        let cells: Vec<_> = row.select(cell_selector).collect();
+        if cells.len() >= 2 {
+            let header_text = cells[0].text().collect::<String>().to_lowercase();
+            if header_text.contains("description") {
+                let desc_text = cells[1].text().collect::<Vec<_>>().join(" ");
+                let cleaned = clean_text(&desc_text);
+                if cleaned.len() > 10 && !cleaned.starts_with("http") && !cleaned.contains("github.com") {
+                    return Ok(truncate(&cleaned, 500));
+                }
+            }
+        }
+    }
+    
+    let features = extract_features(document)?;
+    if !features.is_empty() {
+        return Ok(truncate(&features.join(" "), 500));
+    }
+    
+    // Try any div with text content as fallback
+    for element in document.select(CONTENT_BLOCKS.get()?) {
+        let text = element.text().collect::<Vec<_>>().join(" ");
+        let cleaned = clean_text(&text);
+        if cleaned.len() > 30 
+            && !cleaned.starts_with("http")
+            && !cleaned.contains("github.com")
+            && !cleaned.contains("gamebanana.com")
+            && !cleaned.contains("drive.google.com")
+            && !cleaned.to_lowercase().contains("navigation")
+            && !cleaned.to_lowercase().contains("categories")
+            && !cleaned.to_lowercase().contains("this article is a stub") {
+            return Ok(truncate(&cleaned, 500));
+        }
+    }
+    
+    Ok("No description available".to_string())
+}
+
+/// Returns the page's list items that describe what the mod adds or includes.
+pub fn extract_features(document: &Html) -> Result<Vec<String>> {
+    let mut features: Vec<String> = Vec::new();
+    for item in document.select(LIST_ITEMS.get()?) {
+        let text = item.text().collect::<Vec<_>>().join(" ");
+        let cleaned = clean_text(&text);
+        let lower = cleaned.to_lowercase();
+        if cleaned.len() > 15 
+            && !cleaned.starts_with("http") 
+            && !cleaned.contains("github.com")
+            && (lower.contains("adds") 
+                || lower.contains("features")
+                || lower.contains("includes")
+                || lower.contains("joker"))
+            && !features.contains(&cleaned) {
+            features.push(cleaned);
+        }
+    }
+    Ok(features)
+}
+
+/// Returns true if a cleaned paragraph reads as prose about the mod, rather
+/// than a bare link, a maintenance notice, or a stray section heading.
+fn is_substantive_paragraph(cleaned: &str) -> bool {
+    let lower = cleaned.to_lowercase();
+    cleaned.len() > 20 
+        && !cleaned.starts_with("http")
+        && !cleaned.contains("github.com")
+        && !cleaned.contains("gamebanana.com")
+        && !cleaned.contains("drive.google.com")
+        && !lower.contains("disambiguation")
+        && !lower.contains("redirect")
+        && !lower.contains("this article is a stub")
+        && !lower.contains("bibliography")
+        && !lower.contains("references")
+        && !lower.contains("external links")
+        && !lower.contains("see also")
+        && !lower.contains("categories")
+        && !lower.contains("navigation")
+        && !cleaned.contains("2.1")
+        && !cleaned.contains("2.2")
+        && !cleaned.contains("2.3")
+}
+
+/// Citation markers like `[1]`, `[ 12 ]`, `[edit]`, and `[citation needed]`
+/// left behind by reference lists and section headers.
+static REFERENCE_MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\[\s*(?:\d+|edit|citation needed)\s*\]").unwrap()
+});
+
+/// Whitespace stranded before punctuation once a marker is removed.
+static SPACE_BEFORE_PUNCTUATION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" ([.,;:!?])").unwrap());
+
+/// Collapses whitespace and strips URLs, citation markers, and leftover wiki markup.
+pub fn clean_text(text: &str) -> String {
+    let text = REFERENCE_MARKER.replace_all(text, " ");
+    let text = text.split_whitespace()
+        .filter(|word| !word.starts_with("http") && !word.contains("github.com") && !word.contains("gamebanana.com"))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("[[", "")
+        .replace("]]", "")
+        .replace("{{", "")
+        .replace("}}", "")
+        .replace("()", "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    SPACE_BEFORE_PUNCTUATION.replace_all(&text, "$1").into_owned()
+}
+
+/// Reduces a mod name to a form for loose matching: lowercased, without
+/// parenthetical qualifiers such as "(mod)" or "(Content Mod)", apostrophes
+/// and other punctuation dropped (hyphens, underscores, slashes, and dots
+/// become spaces), and whitespace collapsed.
+pub fn normalize_name(name: &str) -> String {
+    let mut kept = String::new();
+    let mut depth = 0usize;
+    for c in name.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            '-' | '_' | '/' | '.' => kept.push(' '),
+            c if c.is_alphanumeric() || c.is_whitespace() => kept.extend(c.to_lowercase()),
+            _ => {}
+        }
+    }
+    kept.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Truncates to at most `max_len` characters, ending with `...` when cut.
+pub fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else if max_len <= 3 {
+        "...".to_string()
+    } else {
+        let mut result: String = s.chars().take(max_len - 3).collect();
+        result.push_str("...");
+        result
+    }
+}
+
+/// Cuts `s` to about `max_len` characters around the byte range `span`,
+/// keeping the span centered where possible and marking cut ends with
+/// `...`. Returns `s` unchanged if it fits or `max_len` is 0.
+pub fn snippet(s: &str, span: Range<usize>, max_len: usize) -> String {
+    let total = s.chars().count();
+    if max_len == 0 || total <= max_len {
+        return s.to_string();
+    }
+    let span_start = s[..span.start].chars().count();
+    let center = span_start + s[span].chars().count() / 2;
+    let mut start = center.saturating_sub(max_len / 2).min(total - max_len);
+    let mut end = start + max_len;
+    // Make room for the ellipses inside the limit, as `truncate` does
+    if start > 0 {
+        start = (start + 3).min(end);
+    }
+    if end < total {
+        end = end.saturating_sub(3).max(start);
+    }
+    
+    let mut result = String::new();
+    if start > 0 {
+        result.push_str("...");
+    }
+    result.extend(s.chars().skip(start).take(end - start));
+    if end < total {
+        result.push_str("...");
+    }
+    result
+}
+
+/// Returns the page titles of every `[[Title]]`, `[[Title|label]]`, or
+/// `[[Title#Section]]` wikilink in `text`, in order of appearance.
+pub fn wikilinks(text: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else {
+            break;
+        };
+        let title = rest[..end].split(['|', '#']).next().unwrap_or("").trim();
+        if !title.is_empty() {
+            links.push(title.to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+    links
+}
+
+/// Longest slug [`slugify`] returns, leaving room for an extension.
+const MAX_SLUG_LEN: usize = 80;
+
+/// File names Windows reserves regardless of extension.
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "lpt1",
+    "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Turns a mod name into a lowercase ASCII file name stem that is safe on
+/// every platform: non-ASCII letters are transliterated, and spaces,
+/// brackets, and other punctuation become single hyphens. Falls back to
+/// `"mod"` when nothing usable is left.
+pub fn slugify(name: &str) -> String {
+    let ascii = deunicode::deunicode(name).to_lowercase();
+    let mut slug = String::new();
+    for c in ascii.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    let slug = slug.trim_end_matches('-');
+    
+    if slug.is_empty() {
+        "mod".to_string()
+    } else if RESERVED_FILE_NAMES.contains(&slug) {
+        format!("{}-mod", slug)
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_numeric_citations() {
+        assert_eq!(
+            clean_text("Cryptid adds over 100 new Jokers.[1] It also adds new decks.[2][3]"),
+            "Cryptid adds over 100 new Jokers. It also adds new decks.",
+        );
+    }
+
+    #[test]
+    fn strips_spaced_markers_from_joined_text_nodes() {
+        // extract_description joins text nodes with spaces, splitting the brackets apart
+        assert_eq!(
+            clean_text("Features [ edit ] Adds a new Tarot card. [ 4 ]"),
+            "Features Adds a new Tarot card.",
+        );
+    }
+
+    #[test]
+    fn strips_edit_and_citation_needed_in_any_case() {
+        assert_eq!(
+            clean_text("Installation [Edit] Requires Steamodded [citation needed]."),
+            "Installation Requires Steamodded.",
+        );
+    }
+
+    #[test]
+    fn collapses_flattened_newlines_and_leftover_double_spaces() {
+        assert_eq!(
+            clean_text("A mod that\n\n  adds {{ Jokers }}\t and () decks"),
+            "A mod that adds Jokers and decks",
+        );
+    }
+
+    #[test]
+    fn slugs_are_safe_file_names() {
+        assert_eq!(slugify("Joker Pack (Beta)"), "joker-pack-beta");
+        assert_eq!(slugify("  Cryptid!! "), "cryptid");
+        assert_eq!(slugify("Pokémon Jokers"), "pokemon-jokers");
+        assert_eq!(slugify("Ünïcödé / Mod: 2.0"), "unicode-mod-2-0");
+        assert_eq!(slugify("CON"), "con-mod");
+        assert_eq!(slugify("???"), "mod");
+        assert!(slugify(&"long name ".repeat(20)).len() <= MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn normalized_names_ignore_qualifiers_and_punctuation() {
+        assert_eq!(normalize_name("Cryptid (Content Mod)"), "cryptid");
+        assert_eq!(normalize_name("Jen's  Almanac (mod)"), "jens almanac");
+        assert_eq!(normalize_name("Joker-Pack_2.0"), "joker pack 2 0");
+        assert_eq!(normalize_name("  JOKER   pack "), "joker pack");
+    }
+
+    #[test]
+    fn snippets_center_on_the_span_within_the_limit() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        let span = |word: &str| text.find(word).map(|start| start..start + word.len()).unwrap();
+        
+        assert_eq!(snippet(text, span("fox"), 20), "...brown fox jump...");
+        assert_eq!(snippet(text, span("lazy"), 20), "...over the lazy dog");
+        assert_eq!(snippet(text, span("quick"), 20), truncate(text, 20));
+        assert_eq!(snippet(text, span("dog"), 0), text);
+    }
+
+    #[test]
+    fn keeps_bracketed_text_that_is_not_a_marker() {
+        assert_eq!(clean_text("Adds [Rare] jokers and [x2] mult"), "Adds [Rare] jokers and [x2] mult");
+    }
+}
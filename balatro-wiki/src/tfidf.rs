@@ -0,0 +1,123 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::words;
+use crate::database::{ModDatabase, ModInfo};
+
+/// A bag-of-words TF-IDF model over mod names and descriptions, used for
+/// semantic search. Stored as `index.json` next to the mod cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TfIdfIndex {
+    /// `last_updated` of the database the index was built from.
+    pub db_updated: String,
+    idf: HashMap<String, f64>,
+    /// Unit-length TF-IDF vector for each mod, keyed by mod name.
+    vectors: HashMap<String, HashMap<String, f64>>,
+}
+
+impl TfIdfIndex {
+    /// Builds the index from every mod in `db`.
+    pub fn build(db: &ModDatabase) -> Self {
+        let term_counts: HashMap<&str, HashMap<String, f64>> = db.mods.values()
+            .map(|mod_info| (mod_info.name.as_str(), term_counts(&document(mod_info))))
+            .collect();
+        
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for counts in term_counts.values() {
+            for term in counts.keys() {
+                *document_frequency.entry(term).or_default() += 1;
+            }
+        }
+        let total = term_counts.len() as f64;
+        let idf: HashMap<String, f64> = document_frequency.into_iter()
+            .map(|(term, df)| (term.to_string(), (1.0 + total / df as f64).ln()))
+            .collect();
+        
+        let vectors = term_counts.into_iter()
+            .map(|(name, counts)| (name.to_string(), weigh(counts, &idf)))
+            .collect();
+        
+        Self { db_updated: db.last_updated.clone(), idf, vectors }
+    }
+
+    /// Returns true if the index was built from `db` as it is now.
+    pub fn is_current(&self, db: &ModDatabase) -> bool {
+        self.db_updated == db.last_updated && self.vectors.len() == db.mods.len()
+    }
+
+    /// Scores every indexed mod by cosine similarity (0.0–1.0) to `query`,
+    /// returning the non-zero matches best first.
+    pub fn query(&self, query: &str) -> Vec<(String, f64)> {
+        let query_vector = weigh(term_counts(query), &self.idf);
+        if query_vector.is_empty() {
+            return Vec::new();
+        }
+        
+        let mut scored: Vec<(String, f64)> = self.vectors.iter()
+            .map(|(name, vector)| {
+                let similarity: f64 = query_vector.iter()
+                    .filter_map(|(term, weight)| vector.get(term).map(|w| w * weight))
+                    .sum();
+                (name.clone(), similarity)
+            })
+            .filter(|(_, similarity)| *similarity > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scored
+    }
+
+    /// Returns the index file that sits beside `cache_path`.
+    pub fn path_for(cache_path: &Path) -> PathBuf {
+        cache_path.with_file_name("index.json")
+    }
+
+    /// Loads a saved index, or `None` if none has been built.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Writes the index to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+fn document(mod_info: &ModInfo) -> String {
+    format!("{} {}", mod_info.name, mod_info.description)
+}
+
+/// Counts terms in `text`, folding simple plurals ("jokers" → "joker").
+fn term_counts(text: &str) -> HashMap<String, f64> {
+    let mut counts = HashMap::new();
+    for word in words(text) {
+        let term = match word.strip_suffix('s') {
+            Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        };
+        *counts.entry(term).or_default() += 1.0;
+    }
+    counts
+}
+
+/// Weighs raw term counts by IDF and scales the result to unit length.
+/// Terms the index has never seen are dropped.
+fn weigh(counts: HashMap<String, f64>, idf: &HashMap<String, f64>) -> HashMap<String, f64> {
+    let mut vector: HashMap<String, f64> = counts.into_iter()
+        .filter_map(|(term, count)| idf.get(&term).map(|idf| (term, count * idf)))
+        .collect();
+    let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        vector.values_mut().for_each(|w| *w /= norm);
+    }
+    vector
+}
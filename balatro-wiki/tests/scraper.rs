@@ -0,0 +1,661 @@
+use balatro_wiki::{
+    Categories, CategoryDef, HttpCache, LinkStatus, ModDatabase, ModInfo, NotAModPage, ScraperConfig, UpdateTimings,
+    WikiScraper, DEFAULT_MAX_AGE, RECENT_CHANGES_MAX_AGE, RECENT_MODS_MAX_PAGES,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const MOD_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Cryptid - Balatro Mods Wiki</title></head>
+<body>
+<h1 class="firstHeading">Cryptid</h1>
+<div class="mw-parser-output">
+<table class="infobox">
+<tr><td>Author</td><td>MathIsFun</td></tr>
+<tr><td>Version</td><td>0.5.2</td></tr>
+<tr><th>Type</th><td>Joker / Deck, joker</td></tr>
+<tr><td>Download</td><td><a href="https://gamebanana.com/mods/12345">GameBanana</a></td></tr>
+<tr><td>Description</td><td>An explosive mod that adds many new Jokers and decks.</td></tr>
+</table>
+<p>Cryptid is a content mod that pushes Balatro to its absolute limits.</p>
+<ul>
+<li>Adds over 100 new Jokers across every rarity</li>
+<li>Short</li>
+</ul>
+<p><a href="https://github.com/MathIsFun0/Cryptid">Source code</a></p>
+<h2><span class="mw-headline">Downloads</span></h2>
+<ul>
+<li><a href="https://drive.google.com/file/d/abc123/view">Mirror</a></li>
+<li><a href="https://github.com/MathIsFun0/Cryptid/releases/download/v0.5.2/Cryptid.zip">Latest release</a></li>
+<li><a href="/wiki/Steamodded">Steamodded</a></li>
+</ul>
+</div>
+</body>
+</html>"#;
+
+fn category_page(titles: &[&str], cmcontinue: Option<&str>) -> serde_json::Value {
+    let members: Vec<_> = titles.iter().map(|title| json!({ "ns": 0, "title": title })).collect();
+    let mut body = json!({ "query": { "categorymembers": members } });
+    if let Some(token) = cmcontinue {
+        body["continue"] = json!({ "cmcontinue": token, "continue": "-||" });
+    }
+    body
+}
+
+/// A database listing `mods` under "Deck Mods", last updated at `last_updated`.
+fn deck_mods<const N: usize>(mods: [ModInfo; N], last_updated: &str) -> ModDatabase {
+    let mut db = ModDatabase::new();
+    for mut mod_info in mods {
+        mod_info.categories = vec!["Deck Mods".to_string()];
+        db.categories.entry("Deck Mods".to_string()).or_default().push(mod_info.name.clone());
+        db.mods.insert(mod_info.name.clone(), mod_info);
+    }
+    db.last_updated = last_updated.to_string();
+    db
+}
+
+#[tokio::test]
+async fn category_listing_skips_non_mod_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("list", "categorymembers"))
+        .and(query_param("cmtitle", "Category:Joker Mods"))
+        .and(query_param("cmlimit", "500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(
+            &[
+                "Joker Pack",
+                "Category:Old Jokers",
+                "File:Joker.png",
+                "Template:Infobox",
+                "Help:Editing",
+                "User:Someone",
+                "User talk:Someone",
+                "Extra Jokers",
+            ],
+            None,
+        )))
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let names = scraper.scrape_category_page_with_verbosity("Joker%20Mods", 1000, false).await.unwrap();
+
+    assert_eq!(names, vec!["Joker Pack", "Extra Jokers"]);
+}
+
+#[tokio::test]
+async fn category_listing_follows_continuation() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param_is_missing("cmcontinue"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Alpha", "Beta"], Some("page|42|7"))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmcontinue", "page|42|7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Gamma"], None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let names = scraper.scrape_category_page_all("Content Mods").await.unwrap();
+
+    assert_eq!(names, vec!["Alpha", "Beta", "Gamma"]);
+}
+
+#[tokio::test]
+async fn mod_page_is_parsed_into_mod_info() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(MOD_PAGE))
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let mod_info = scraper.scrape_mod_page("Cryptid").await.unwrap();
+
+    assert_eq!(mod_info.name, "Cryptid");
+    assert_eq!(mod_info.github_url.as_deref(), Some("https://github.com/MathIsFun0/Cryptid"));
+    assert_eq!(mod_info.wiki_url, format!("{}/wiki/Cryptid", server.uri()));
+    assert_eq!(mod_info.description, "Cryptid is a content mod that pushes Balatro to its absolute limits.");
+    assert_eq!(mod_info.features, vec!["Adds over 100 new Jokers across every rarity"]);
+    assert_eq!(mod_info.tags, vec!["Joker", "Deck"]);
+    assert_eq!(
+        mod_info.download_urls,
+        vec![
+            "https://gamebanana.com/mods/12345",
+            "https://drive.google.com/file/d/abc123/view",
+            "https://github.com/MathIsFun0/Cryptid/releases/download/v0.5.2/Cryptid.zip",
+        ]
+    );
+    assert_eq!(mod_info.author.as_deref(), Some("MathIsFun"));
+    assert_eq!(mod_info.version.as_deref(), Some("0.5.2"));
+    assert!(mod_info.categories.is_empty());
+    assert!(mod_info.last_updated.is_some());
+}
+
+#[tokio::test]
+async fn unchanged_page_is_reused_from_the_http_cache() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid"))
+        .and(header("If-None-Match", "\"rev-7\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid"))
+        .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"rev-7\"").set_body_string(MOD_PAGE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(HttpCache::default());
+    let scraper = WikiScraper::with_base_url(&server.uri()).with_http_cache(cache.clone());
+    let first = scraper.scrape_mod_page("Cryptid").await.unwrap();
+    let second = scraper.scrape_mod_page("Cryptid").await.unwrap();
+
+    assert_eq!(second.description, first.description);
+    assert_eq!(second.github_url, first.github_url);
+    assert!(cache.get(&format!("{}/wiki/Cryptid", server.uri())).is_some());
+}
+
+#[tokio::test]
+async fn interrupted_full_update_leaves_the_cache_alone_and_a_stale_checkpoint_beside_it() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmtitle", "Category:Deck Mods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Alpha", "Beta"], None)))
+        .mount(&server)
+        .await;
+    for name in ["Alpha", "Beta"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/wiki/{}", name)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                MOD_PAGE.replace("Cryptid", name).replace("github.com", "gitlab.com"),
+            ))
+            .mount(&server)
+            .await;
+    }
+
+    let cache_dir = std::env::temp_dir().join(format!("balatro-wiki-checkpoint-{}", std::process::id()));
+    let cache_path = cache_dir.join("mods.json");
+    let config = ScraperConfig {
+        base_url: server.uri(),
+        request_delay: Duration::ZERO,
+        categories: Categories::new(vec![CategoryDef {
+            name: "Deck Mods".to_string(),
+            wiki_category: "Deck%20Mods".to_string(),
+            aliases: Vec::new(),
+        }]),
+        ..ScraperConfig::default()
+    };
+    let good = deck_mods(["Alpha", "Beta", "Gamma"].map(ModInfo::named), "2024-03-01T00:00:00Z");
+    good.save_to(&cache_path).unwrap();
+
+    // The finished crawl is never saved, as if the update were interrupted
+    let scraper = WikiScraper::from_config(config).with_checkpoints(&cache_path, 1);
+    let db = scraper.update_database_with_verbosity(false).await.unwrap();
+    let cache = ModDatabase::load_from(&cache_path).unwrap();
+    let partial = ModDatabase::load_from(&ModDatabase::partial_path_for(&cache_path)).unwrap();
+    std::fs::remove_file(&cache_path).unwrap();
+    let without_cache = ModDatabase::load_from(&cache_path).unwrap();
+    std::fs::remove_dir_all(&cache_dir).unwrap();
+
+    assert_eq!(db.mods.len(), 2);
+    assert_eq!(cache.mods.len(), 3);
+    assert_eq!(cache.last_updated, "2024-03-01T00:00:00Z");
+    assert_eq!(partial.mods.len(), 1);
+    assert_eq!(partial.categories["Deck Mods"].len(), 1);
+    assert!(partial.should_update(DEFAULT_MAX_AGE));
+    assert_eq!(without_cache.mods.len(), 1);
+}
+
+#[tokio::test]
+async fn full_update_reuses_mods_whose_page_is_unchanged() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmtitle", "Category:Deck Mods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Alpha", "Beta"], None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("prop", "revisions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": {
+                "pages": {
+                    "1": { "title": "Alpha", "revisions": [{ "timestamp": "2024-03-01T00:00:00Z" }] },
+                    "2": { "title": "Beta", "revisions": [{ "timestamp": "2024-03-02T00:00:00Z" }] }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    for (name, fetches) in [("Alpha", 0), ("Beta", 1)] {
+        Mock::given(method("GET"))
+            .and(path(format!("/wiki/{}", name)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                MOD_PAGE.replace("Cryptid", name).replace("github.com", "gitlab.com"),
+            ))
+            .expect(fetches)
+            .mount(&server)
+            .await;
+    }
+
+    let cached = |name: &str, last_edited: &str| ModInfo {
+        description: "Cached copy".to_string(),
+        last_edited: Some(last_edited.to_string()),
+        ..ModInfo::named(name)
+    };
+    let previous = deck_mods(
+        [cached("Alpha", "2024-03-01T00:00:00Z"), cached("Beta", "2024-01-01T00:00:00Z")],
+        "2024-03-01T12:00:00Z",
+    );
+    let config = ScraperConfig {
+        base_url: server.uri(),
+        request_delay: Duration::ZERO,
+        categories: Categories::new(vec![CategoryDef {
+            name: "Deck Mods".to_string(),
+            wiki_category: "Deck%20Mods".to_string(),
+            aliases: Vec::new(),
+        }]),
+        ..ScraperConfig::default()
+    };
+    let scraper = WikiScraper::from_config(config).with_previous(Arc::new(previous));
+    let mut unchanged = 0;
+    let db = scraper
+        .update_database_with_progress(false, |progress| unchanged = progress.unchanged)
+        .await
+        .unwrap();
+
+    assert_eq!(unchanged, 1);
+    assert_eq!(scraper.request_count(), 3);
+    assert_eq!(db.mods["Alpha"].description, "Cached copy");
+    assert_ne!(db.mods["Beta"].description, "Cached copy");
+    assert_eq!(db.mods["Beta"].last_edited.as_deref(), Some("2024-03-02T00:00:00Z"));
+}
+
+#[tokio::test]
+async fn full_update_records_failures_unless_strict() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmtitle", "Category:Deck Mods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Alpha", "Beta"], None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmtitle", "Category:Seal Mods"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Alpha"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            MOD_PAGE.replace("Cryptid", "Alpha").replace("github.com", "gitlab.com"),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Beta"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+        .mount(&server)
+        .await;
+
+    let category = |name: &str, wiki_category: &str| CategoryDef {
+        name: name.to_string(),
+        wiki_category: wiki_category.to_string(),
+        aliases: Vec::new(),
+    };
+    let config = ScraperConfig {
+        base_url: server.uri(),
+        request_delay: Duration::ZERO,
+        timeout: Duration::from_millis(200),
+        categories: Categories::new(vec![category("Deck Mods", "Deck%20Mods"), category("Seal Mods", "Seal%20Mods")]),
+        ..ScraperConfig::default()
+    };
+    let scraper = WikiScraper::from_config(config).with_quiet_errors(true);
+    let (db, _, failures) = scraper.update_database_streaming(false, |_| {}, |_| {}).await.unwrap();
+
+    assert_eq!(db.mods.len(), 1);
+    assert_eq!(failures.categories, ["Seal Mods"]);
+    assert_eq!(failures.mods, ["Beta"]);
+    assert_eq!(failures.len(), 2);
+    assert!(scraper.with_strict(true).update_database_with_verbosity(false).await.is_err());
+}
+
+#[tokio::test]
+async fn category_update_keeps_cached_mods_whose_page_failed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("cmtitle", "Category:Deck Mods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_page(&["Alpha", "Beta"], None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Alpha"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            MOD_PAGE.replace("Cryptid", "Alpha").replace("github.com", "gitlab.com"),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Beta"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+        .mount(&server)
+        .await;
+
+    let cached = |name: &str| ModInfo { description: "Cached".to_string(), ..ModInfo::named(name) };
+    let mut db = deck_mods(["Alpha", "Beta", "Gamma"].map(cached), "2024-03-01T00:00:00Z");
+    let config = ScraperConfig {
+        base_url: server.uri(),
+        request_delay: Duration::ZERO,
+        timeout: Duration::from_millis(200),
+        ..ScraperConfig::default()
+    };
+    let scraper = WikiScraper::from_config(config).with_quiet_errors(true);
+    let failures = scraper.update_category(&mut db, "Deck Mods", false).await.unwrap();
+
+    assert_eq!(failures.mods, ["Beta"]);
+    assert_eq!(db.categories["Deck Mods"], ["Alpha", "Beta"]);
+    assert_ne!(db.mods["Alpha"].description, "Cached");
+    assert_eq!(db.mods["Beta"].description, "Cached");
+    assert_eq!(db.mods["Beta"].categories, ["Deck Mods"]);
+    assert!(!db.mods.contains_key("Gamma"));
+}
+
+#[tokio::test]
+async fn incremental_update_stays_stale_after_a_failure_and_refuses_a_too_old_start() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("list", "recentchanges"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": { "recentchanges": [{ "title": "Alpha" }, { "title": "Beta" }] }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Alpha"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            MOD_PAGE.replace("Cryptid", "Alpha").replace("github.com", "gitlab.com"),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Beta"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+        .mount(&server)
+        .await;
+
+    let cached = |name: &str| ModInfo { description: "Cached".to_string(), ..ModInfo::named(name) };
+    let mut db = deck_mods(["Alpha", "Beta"].map(cached), "2024-03-01T00:00:00Z");
+    let config = ScraperConfig {
+        base_url: server.uri(),
+        request_delay: Duration::ZERO,
+        timeout: Duration::from_millis(200),
+        ..ScraperConfig::default()
+    };
+    let scraper = WikiScraper::from_config(config).with_quiet_errors(true);
+    let since = chrono::Utc::now() - chrono::Duration::days(1);
+    let (refreshed, failures) = scraper.update_changed_since(&mut db, since, false).await.unwrap();
+    let too_old = chrono::Utc::now() - chrono::Duration::from_std(RECENT_CHANGES_MAX_AGE).unwrap()
+        - chrono::Duration::days(1);
+
+    assert_eq!(refreshed, 1);
+    assert_eq!(failures.mods, ["Beta"]);
+    assert_eq!(db.last_updated, "2024-03-01T00:00:00Z");
+    assert!(db.category_updated.is_empty());
+    assert!(scraper.changed_pages_since(too_old).await.is_err());
+}
+
+#[test]
+fn update_timings_report_the_median_and_slowest_pages() {
+    let ms = Duration::from_millis;
+    let mut timings = UpdateTimings {
+        per_mod: vec![("Alpha".to_string(), ms(30)), ("Beta".to_string(), ms(10)), ("Gamma".to_string(), ms(50))],
+        ..UpdateTimings::default()
+    };
+    let slowest: Vec<&str> = timings.slowest(2).iter().map(|(name, _)| name.as_str()).collect();
+
+    assert_eq!(slowest, ["Gamma", "Alpha"]);
+    assert_eq!(timings.median(), Some(ms(30)));
+    timings.per_mod.push(("Delta".to_string(), ms(30)));
+    timings.per_mod.push(("Epsilon".to_string(), ms(70)));
+    timings.per_mod.push(("Zeta".to_string(), ms(70)));
+    assert_eq!(timings.median(), Some(ms(40)));
+    assert_eq!(timings.slowest(2)[1].0, "Zeta");
+    assert_eq!(UpdateTimings::default().median(), None);
+}
+
+#[tokio::test]
+async fn github_lookups_stop_after_a_rate_limit_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/MathIsFun0/Cryptid/releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "tag_name": "v0.5.2" }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/jenwalter666/JensAlmanac/releases"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = ScraperConfig {
+        request_delay: Duration::ZERO,
+        github_api_url: server.uri(),
+        ..ScraperConfig::default()
+    };
+    let scraper = WikiScraper::from_config(config);
+    let cryptid = "https://github.com/MathIsFun0/Cryptid";
+
+    assert!(scraper.has_github_releases(cryptid).await.unwrap());
+    assert!(scraper.has_github_releases("https://github.com/jenwalter666/JensAlmanac").await.is_err());
+    assert!(scraper.clone().has_github_releases(cryptid).await.is_err());
+    assert_eq!(scraper.request_count(), 2);
+}
+
+#[tokio::test]
+async fn redirect_page_is_followed_to_its_target() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid_(mod)"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<html><body><div class="redirectMsg"><a href="/wiki/Cryptid">Cryptid</a></div></body></html>"#,
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(MOD_PAGE))
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let mod_info = scraper.scrape_mod_page("Cryptid_(mod)").await.unwrap();
+
+    assert_eq!(mod_info.name, "Cryptid");
+    assert_eq!(mod_info.wiki_url, format!("{}/wiki/Cryptid", server.uri()));
+}
+
+#[tokio::test]
+async fn disambiguation_page_is_not_a_mod() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Jokers"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<html><body><h1 class="firstHeading">Jokers</h1>
+<div class="mw-parser-output"><p>Jokers may refer to:</p><ul><li>Joker Pack</li></ul></div>
+</body></html>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let err = scraper.scrape_mod_page("Jokers").await.unwrap_err();
+
+    assert!(err.is::<NotAModPage>(), "unexpected error: {}", err);
+}
+
+#[tokio::test]
+async fn last_edited_comes_from_latest_revision() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("prop", "revisions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": {
+                "normalized": [{ "from": "jen's Almanac", "to": "Jen's Almanac" }],
+                "pages": {
+                    "7": { "title": "Jen's Almanac", "revisions": [{ "timestamp": "2024-03-02T09:30:00Z" }] },
+                    "-1": { "title": "Gone", "missing": "" }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let mut db = ModDatabase::new();
+    for name in ["jen's Almanac", "Gone"] {
+        db.mods.insert(name.to_string(), ModInfo::named(name));
+    }
+    db.last_updated = "2024-03-03T00:00:00Z".to_string();
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let updated = scraper.fill_last_edited(&mut db).await.unwrap();
+
+    assert_eq!(updated, 1);
+    assert_eq!(db.mods["jen's Almanac"].last_edited.as_deref(), Some("2024-03-02T09:30:00Z"));
+    assert_eq!(db.mods["Gone"].last_edited, None);
+}
+
+#[tokio::test]
+async fn recent_mods_skips_pages_outside_mod_categories() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("list", "recentchanges"))
+        .and(query_param("rcend", "2024-03-01T00:00:00Z"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": { "recentchanges": [
+                { "title": "Main Page", "timestamp": "2024-03-03T10:00:00Z" },
+                { "title": "Cryptid", "timestamp": "2024-03-02T09:30:00Z" },
+                { "title": "Cryptid", "timestamp": "2024-03-01T08:00:00Z" }
+            ] }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("titles", "Main Page|Cryptid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": { "pages": {
+                "1": { "title": "Main Page", "categories": [] },
+                "2": { "title": "Cryptid", "categories": [{ "title": "Category:Content Mods" }] }
+            } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Cryptid"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(MOD_PAGE))
+        .mount(&server)
+        .await;
+
+    let mut db = ModDatabase::new();
+    db.categories.insert("Content Mods".to_string(), Vec::new());
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let since = "2024-03-01T00:00:00Z".parse().unwrap();
+    let mods = scraper.recent_mods(&db, 5, Some(since), false).await.unwrap();
+
+    assert_eq!(mods.len(), 1);
+    assert_eq!(mods[0].name, "Cryptid");
+    assert_eq!(mods[0].categories, vec!["Content Mods"]);
+    assert_eq!(mods[0].last_edited.as_deref(), Some("2024-03-02T09:30:00Z"));
+}
+
+#[tokio::test]
+async fn recent_mods_reads_a_bounded_number_of_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("list", "recentchanges"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": { "recentchanges": [{ "title": "Main Page", "timestamp": "2024-03-03T10:00:00Z" }] },
+            "continue": { "rccontinue": "20240303100000|1", "continue": "-||" }
+        })))
+        .expect(RECENT_MODS_MAX_PAGES as u64)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/w/api.php"))
+        .and(query_param("titles", "Main Page"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "query": { "pages": { "1": { "title": "Main Page", "categories": [] } } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut db = ModDatabase::new();
+    db.categories.insert("Content Mods".to_string(), Vec::new());
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let mods = scraper.recent_mods(&db, 5, None, false).await.unwrap();
+
+    assert!(mods.is_empty());
+}
+
+#[tokio::test]
+async fn links_are_classified_as_ok_redirected_or_broken() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/live"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/moved"))
+        .respond_with(ResponseTemplate::new(301).insert_header("Location", "/live"))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/moved-to-nowhere"))
+        .respond_with(ResponseTemplate::new(301).insert_header("Location", "/gone"))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/gone"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let scraper = WikiScraper::with_base_url(&server.uri());
+    let check = |page: &str| {
+        let url = format!("{}{}", server.uri(), page);
+        let scraper = scraper.clone();
+        async move { scraper.check_link(&url).await.unwrap() }
+    };
+
+    assert_eq!(check("/live").await, LinkStatus::Ok);
+    assert_eq!(check("/moved").await, LinkStatus::Redirected(format!("{}/live", server.uri())));
+    assert_eq!(check("/moved-to-nowhere").await, LinkStatus::Broken(404));
+    assert_eq!(check("/gone").await, LinkStatus::Broken(404));
+}